@@ -0,0 +1,239 @@
+use crate::union_find::UnionFind;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 複数スレッドから&selfで操作できるロックフリーなUnionFind
+/// 結合はルートの親をCASで書き換え、探索は経路半減で木を平らにする
+/// どちらのルートを親にするかはnode番号のハッシュで決め、木の高さの偏りを防ぐ
+#[derive(Debug)]
+pub struct ConcurrentUnionFind {
+    // 親 ルートノードであるときは自身
+    parent: Vec<AtomicUsize>,
+    root_count: AtomicUsize,
+}
+
+impl ConcurrentUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).map(AtomicUsize::new).collect(),
+            root_count: AtomicUsize::new(size),
+        }
+    }
+
+    /// node数を取得
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// node数が0か判定
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// ルートの数を取得
+    pub fn get_root_count(&self) -> usize {
+        self.root_count.load(Ordering::Acquire)
+    }
+
+    /// nodeのroot_nodeを取得
+    /// nodeの親を祖父に付け替えながら上る (経路半減)
+    pub fn find(&self, node: usize) -> usize {
+        let mut node_tmp = node;
+        loop {
+            let parent = self.parent[node_tmp].load(Ordering::Acquire);
+            if parent == node_tmp {
+                return node_tmp;
+            }
+            let grandparent = self.parent[parent].load(Ordering::Acquire);
+            if parent != grandparent {
+                // 失敗しても他スレッドが親を更新しただけなので無視してよい
+                let _ = self.parent[node_tmp].compare_exchange_weak(
+                    parent,
+                    grandparent,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                );
+            }
+            node_tmp = grandparent;
+        }
+    }
+
+    /// node_aとnode_bが同一のグループか判定
+    pub fn same(&self, node_a: usize, node_b: usize) -> bool {
+        loop {
+            let root_a = self.find(node_a);
+            let root_b = self.find(node_b);
+            if root_a == root_b {
+                return true;
+            }
+            // root_aがまだルートなら、この時点で別グループだったことが確定する
+            if self.parent[root_a].load(Ordering::Acquire) == root_a {
+                return false;
+            }
+        }
+    }
+
+    /// node_aとnode_bそれぞれを含むグループを結合する
+    /// true: 結合した時
+    /// false: 既に同一グループだった時
+    pub fn merge(&self, node_a: usize, node_b: usize) -> bool {
+        loop {
+            let root_a = self.find(node_a);
+            let root_b = self.find(node_b);
+            if root_a == root_b {
+                return false;
+            }
+            let (root_parent, root_child) = if priority(root_a) > priority(root_b) {
+                (root_a, root_b)
+            } else {
+                (root_b, root_a)
+            };
+            // root_childがまだルートの場合のみ付け替える 失敗したら探索からやり直す
+            if self.parent[root_child]
+                .compare_exchange(root_child, root_parent, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                self.root_count.fetch_sub(1, Ordering::AcqRel);
+                return true;
+            }
+        }
+    }
+
+    /// 現在のグループ分けをUnionFindに変換する
+    /// 結合に使った辺の数は引き継がず、各グループは木 (辺数 = node数 - 1) として扱われる
+    /// 他スレッドが操作していない時に呼ぶこと
+    pub fn to_union_find(&self) -> UnionFind {
+        let mut uf = UnionFind::new(self.len());
+        for node in 0..self.len() {
+            let root = self.find(node);
+            if node != root {
+                uf.merge(node, root);
+            }
+        }
+        uf
+    }
+}
+
+// ルート同士を結合する時、優先度の高い方を親にする
+// node番号を混ぜたハッシュを使い、ランダムな順序で結合したのと同じ効果を得る
+fn priority(node: usize) -> (u64, usize) {
+    let mut x = (node as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (x ^ (x >> 31), node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // テスト用の疑似乱数 (xorshift)
+    fn random_edges(n: usize, m: usize, seed: u64) -> Vec<(usize, usize)> {
+        let mut x = seed;
+        let mut next = move || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        (0..m)
+            .map(|_| ((next() % n as u64) as usize, (next() % n as u64) as usize))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_thread() {
+        let uf = ConcurrentUnionFind::new(5);
+        assert!(uf.merge(0, 1));
+        assert!(uf.merge(1, 2));
+        assert!(!uf.merge(2, 0));
+        assert!(uf.merge(3, 4));
+        assert!(uf.same(0, 2));
+        assert!(!uf.same(0, 3));
+        assert_eq!(uf.find(1), uf.find(2));
+        assert_eq!(uf.get_root_count(), 2);
+        assert_eq!(uf.len(), 5);
+    }
+
+    #[test]
+    fn test_to_union_find() {
+        let uf = ConcurrentUnionFind::new(6);
+        uf.merge(5, 1);
+        uf.merge(4, 1);
+        uf.merge(0, 2);
+        let mut sequential = uf.to_union_find();
+        assert_eq!(
+            sequential.groups(),
+            vec![vec![0, 2], vec![1, 4, 5], vec![3]]
+        );
+        assert_eq!(sequential.get_root_and_size(4).1, 3);
+        // 辺数は各グループを木とみなした値になる
+        assert_eq!(sequential.edge_count(1), 2);
+        assert_eq!(sequential.edge_count(3), 0);
+        assert!(sequential.is_tree(0));
+        assert!(sequential.is_tree(3));
+        assert!(!sequential.is_unicyclic(3));
+    }
+
+    #[test]
+    fn test_stress_against_union_find() {
+        let n = 5000;
+        let thread_count = 8;
+        for seed in 1..=4 {
+            let edges = random_edges(n, 4000, seed * 0x2545_f491_4f6c_dd1d);
+            let uf = ConcurrentUnionFind::new(n);
+            let merged_count = AtomicUsize::new(0);
+            thread::scope(|s| {
+                for chunk in edges.chunks(edges.len() / thread_count) {
+                    let uf = &uf;
+                    let merged_count = &merged_count;
+                    s.spawn(move || {
+                        for &(a, b) in chunk {
+                            if uf.merge(a, b) {
+                                merged_count.fetch_add(1, Ordering::Relaxed);
+                            }
+                            // 結合と並行して問い合わせも行う
+                            assert!(uf.same(a, b));
+                        }
+                    });
+                }
+            });
+
+            let mut sequential = UnionFind::new(n);
+            for &(a, b) in &edges {
+                sequential.merge(a, b);
+            }
+            assert_eq!(uf.get_root_count(), sequential.get_root_count());
+            assert_eq!(n - merged_count.into_inner(), sequential.get_root_count());
+            assert_eq!(uf.to_union_find().groups(), sequential.groups());
+        }
+    }
+
+    #[test]
+    fn test_stress_duplicate_merges() {
+        // 全スレッドが同じ辺を結合しても、結合に成功するのは1回だけ
+        let n = 2000;
+        let edges = random_edges(n, 3000, 0x1234_5678_9abc_def1);
+        let uf = ConcurrentUnionFind::new(n);
+        let merged_count = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for t in 0..4 {
+                let (uf, edges, merged_count) = (&uf, &edges, &merged_count);
+                s.spawn(move || {
+                    for i in 0..edges.len() {
+                        let (a, b) = edges[(i + t * 700) % edges.len()];
+                        if uf.merge(a, b) {
+                            merged_count.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+        let mut sequential = UnionFind::new(n);
+        for &(a, b) in &edges {
+            sequential.merge(a, b);
+        }
+        assert_eq!(n - merged_count.into_inner(), sequential.get_root_count());
+        assert_eq!(uf.to_union_find().groups(), sequential.groups());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::union_find::UnionFind;
+
+/// 要素をグループから取り外せるUnionFind
+/// 要素を取り外す時は古いnodeを捨てて新しいnode(代理node)を割り当てる
+/// 要素の番号は0..sizeのまま変わらず、内部のnode番号との対応はnode_of / element_ofで取得できる
+#[derive(Debug, Clone)]
+pub struct DeletableUnionFind {
+    uf: UnionFind,
+    // 要素 -> 現在のnode
+    node_of: Vec<usize>,
+    // node -> 要素 捨てられたnodeの場合はNone
+    element_of: Vec<Option<usize>>,
+    // ルートノードであるとき->グループ内の捨てられていないnodeの数
+    live_size: Vec<usize>,
+    root_count: usize,
+}
+
+impl DeletableUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            uf: UnionFind::new(size),
+            node_of: (0..size).collect(),
+            element_of: (0..size).map(Some).collect(),
+            live_size: vec![1; size],
+            root_count: size,
+        }
+    }
+
+    /// 要素数を取得
+    pub fn len(&self) -> usize {
+        self.node_of.len()
+    }
+
+    /// 要素数が0か判定
+    pub fn is_empty(&self) -> bool {
+        self.node_of.is_empty()
+    }
+
+    /// ルートの数 (要素を1つ以上含むグループの数) を取得
+    pub fn get_root_count(&self) -> usize {
+        self.root_count
+    }
+
+    /// 要素elementに現在割り当てられているnodeを取得
+    pub fn node_of(&self, element: usize) -> usize {
+        self.node_of[element]
+    }
+
+    /// nodeが割り当てられている要素を取得 捨てられたnodeの場合はNone
+    pub fn element_of(&self, node: usize) -> Option<usize> {
+        self.element_of[node]
+    }
+
+    /// element_aとelement_bそれぞれを含むグループを結合する
+    /// false: 既に同一グループだった時
+    pub fn merge(&mut self, element_a: usize, element_b: usize) -> bool {
+        match self
+            .uf
+            .merge(self.node_of[element_a], self.node_of[element_b])
+        {
+            Some((new_root, absorbed_root)) => {
+                self.live_size[new_root] += self.live_size[absorbed_root];
+                self.root_count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// elementを現在のグループから取り外し、単独のグループにする
+    /// false: 既に単独のグループだった時
+    pub fn isolate(&mut self, element: usize) -> bool {
+        let old_node = self.node_of[element];
+        let root = self.uf.get_root_and_size(old_node).0;
+        if self.live_size[root] == 1 {
+            return false;
+        }
+        self.live_size[root] -= 1;
+        self.element_of[old_node] = None;
+
+        let new_node = self.uf.add_node();
+        self.live_size.push(1);
+        self.element_of.push(Some(element));
+        self.node_of[element] = new_node;
+        self.root_count += 1;
+        true
+    }
+
+    /// elementを現在のグループから取り外し、targetのグループに移す
+    /// false: 既に同一グループだった時
+    pub fn move_to(&mut self, element: usize, target: usize) -> bool {
+        if self.same(element, target) {
+            return false;
+        }
+        self.isolate(element);
+        self.merge(element, target)
+    }
+
+    /// element_aとelement_bが同一のグループか判定
+    pub fn same(&mut self, element_a: usize, element_b: usize) -> bool {
+        self.uf
+            .same(self.node_of[element_a], self.node_of[element_b])
+    }
+
+    /// elementが属するグループの要素数を取得
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.uf.get_root_and_size(self.node_of[element]).0;
+        self.live_size[root]
+    }
+
+    /// グループごとの要素のリストを取得
+    /// グループは最小の要素番号順、グループ内は昇順
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_index = vec![usize::MAX; self.element_of.len()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for element in 0..self.len() {
+            let root = self.uf.get_root_and_size(self.node_of[element]).0;
+            if group_index[root] == usize::MAX {
+                group_index[root] = groups.len();
+                groups.push(vec![]);
+            }
+            groups[group_index[root]].push(element);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut uf = DeletableUnionFind::new(5);
+        assert!(uf.merge(0, 1));
+        assert!(uf.merge(1, 2));
+        assert!(!uf.merge(2, 0));
+        assert_eq!(uf.get_root_count(), 3);
+        assert_eq!(uf.size(2), 3);
+        assert!(uf.same(0, 2));
+    }
+
+    #[test]
+    fn test_isolate() {
+        let mut uf = DeletableUnionFind::new(5);
+        uf.merge(0, 1);
+        uf.merge(1, 2);
+        assert!(uf.isolate(1));
+        assert_eq!(uf.get_root_count(), 4);
+        assert_eq!(uf.size(0), 2);
+        assert_eq!(uf.size(1), 1);
+        // 取り外した要素以外の繋がりは保たれる
+        assert!(uf.same(0, 2));
+        assert!(!uf.same(0, 1));
+        // 単独の要素は取り外せない
+        assert!(!uf.isolate(1));
+        assert!(!uf.isolate(4));
+        assert_eq!(uf.get_root_count(), 4);
+    }
+
+    #[test]
+    fn test_move_to() {
+        let mut uf = DeletableUnionFind::new(6);
+        uf.merge(0, 1);
+        uf.merge(1, 2);
+        uf.merge(3, 4);
+        assert!(uf.move_to(2, 3));
+        assert_eq!(uf.groups(), vec![vec![0, 1], vec![2, 3, 4], vec![5]]);
+        assert_eq!(uf.get_root_count(), 3);
+        assert_eq!(uf.size(4), 3);
+        assert_eq!(uf.size(0), 2);
+        assert!(!uf.move_to(2, 4));
+        // 単独の要素の移動ではグループが1つ減る
+        assert!(uf.move_to(5, 0));
+        assert_eq!(uf.get_root_count(), 2);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 5], vec![2, 3, 4]]);
+        // 移動を繰り返しても要素数は正しい
+        assert!(uf.move_to(0, 2));
+        assert!(uf.move_to(1, 2));
+        assert_eq!(uf.groups(), vec![vec![0, 1, 2, 3, 4], vec![5]]);
+        assert_eq!(uf.size(5), 1);
+        assert_eq!(uf.get_root_count(), 2);
+    }
+
+    #[test]
+    fn test_node_mapping() {
+        let mut uf = DeletableUnionFind::new(3);
+        uf.merge(0, 1);
+        assert_eq!(uf.node_of(1), 1);
+        uf.move_to(1, 2);
+        let new_node = uf.node_of(1);
+        assert_eq!(new_node, 3);
+        assert_eq!(uf.element_of(new_node), Some(1));
+        assert_eq!(uf.element_of(1), None);
+        assert_eq!(uf.element_of(2), Some(2));
+        assert_eq!(uf.len(), 3);
+    }
+}
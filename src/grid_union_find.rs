@@ -0,0 +1,289 @@
+use crate::union_find::UnionFind;
+
+/// グリッド上で隣接とみなす方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// 上下左右
+    Four,
+    /// 上下左右と斜め
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// (row, col)で指定するグリッド上のUnionFind
+/// activateしたセル同士のみが隣接していれば同一グループになる
+#[derive(Debug, Clone)]
+pub struct GridUnionFind {
+    height: usize,
+    width: usize,
+    connectivity: Connectivity,
+    active: Vec<bool>,
+    active_count: usize,
+    uf: UnionFind,
+}
+
+impl GridUnionFind {
+    /// 全セルが非アクティブの状態で作成
+    pub fn new(height: usize, width: usize, connectivity: Connectivity) -> Self {
+        Self {
+            height,
+            width,
+            connectivity,
+            active: vec![false; height * width],
+            active_count: 0,
+            uf: UnionFind::new(height * width),
+        }
+    }
+
+    /// grid[row][col]がtrueのセルをアクティブにした状態で作成
+    /// 全ての行は同じ長さであること
+    pub fn from_grid(grid: &[Vec<bool>], connectivity: Connectivity) -> Self {
+        let height = grid.len();
+        let width = grid.first().map_or(0, |row| row.len());
+        assert!(
+            grid.iter().all(|row| row.len() == width),
+            "gridの行の長さが揃っていません"
+        );
+        let mut guf = Self::new(height, width, connectivity);
+        for (row, line) in grid.iter().enumerate() {
+            for (col, &is_active) in line.iter().enumerate() {
+                if is_active {
+                    guf.activate((row, col));
+                }
+            }
+        }
+        guf
+    }
+
+    /// グリッドの (高さ, 幅) を取得
+    pub fn shape(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// cellがアクティブか判定
+    pub fn is_active(&self, cell: (usize, usize)) -> bool {
+        self.active[self.index(cell)]
+    }
+
+    /// cellをアクティブにし、隣接するアクティブなセルと結合する
+    /// false: 既にアクティブだった時
+    pub fn activate(&mut self, cell: (usize, usize)) -> bool {
+        let index = self.index(cell);
+        if self.active[index] {
+            return false;
+        }
+        self.active[index] = true;
+        self.active_count += 1;
+        for neighbor in self.neighbors(cell) {
+            if self.is_active(neighbor) {
+                let neighbor_index = self.index(neighbor);
+                self.uf.merge(index, neighbor_index);
+            }
+        }
+        true
+    }
+
+    /// アクティブなセルからなるグループの数を取得
+    pub fn get_root_count(&self) -> usize {
+        self.uf.get_root_count() - (self.height * self.width - self.active_count)
+    }
+
+    /// cell_aとcell_bが同一のグループか判定
+    /// 非アクティブなセルはどのグループにも属さない
+    pub fn same(&mut self, cell_a: (usize, usize), cell_b: (usize, usize)) -> bool {
+        self.is_active(cell_a)
+            && self.is_active(cell_b)
+            && self.uf.same(self.index(cell_a), self.index(cell_b))
+    }
+
+    /// cellが属するグループのセル数を取得 非アクティブなセルは0
+    pub fn size(&mut self, cell: (usize, usize)) -> usize {
+        if self.is_active(cell) {
+            self.uf.get_root_and_size(self.index(cell)).1
+        } else {
+            0
+        }
+    }
+
+    /// グループごとのセルのリストを取得
+    /// グループ、グループ内ともに行優先で先に現れる順
+    pub fn components(&mut self) -> Vec<Vec<(usize, usize)>> {
+        let mut components: Vec<Vec<(usize, usize)>> = vec![];
+        let ids = self.component_ids();
+        for (row, line) in ids.iter().enumerate() {
+            for (col, id) in line.iter().enumerate() {
+                if let Some(id) = *id {
+                    if id == components.len() {
+                        components.push(vec![]);
+                    }
+                    components[id].push((row, col));
+                }
+            }
+        }
+        components
+    }
+
+    /// 各セルのグループ番号を二次元ベクタで取得
+    /// グループ番号はcomponentsのインデックスと一致し、非アクティブなセルはNone
+    pub fn component_ids(&mut self) -> Vec<Vec<Option<usize>>> {
+        let mut id_of_root = vec![usize::MAX; self.height * self.width];
+        let mut next_id = 0;
+        let mut ids = vec![vec![None; self.width]; self.height];
+        for (row, line) in ids.iter_mut().enumerate() {
+            for (col, id) in line.iter_mut().enumerate() {
+                if !self.is_active((row, col)) {
+                    continue;
+                }
+                let root = self.uf.get_root_and_size(self.index((row, col))).0;
+                if id_of_root[root] == usize::MAX {
+                    id_of_root[root] = next_id;
+                    next_id += 1;
+                }
+                *id = Some(id_of_root[root]);
+            }
+        }
+        ids
+    }
+
+    /// 各セルのグループ番号(非アクティブならNone)をfで変換した二次元ベクタを取得
+    /// print_2d_vectorでそのまま出力できる
+    pub fn render<T, F>(&mut self, mut f: F) -> Vec<Vec<T>>
+    where
+        F: FnMut(Option<usize>) -> T,
+    {
+        self.component_ids()
+            .into_iter()
+            .map(|line| line.into_iter().map(&mut f).collect())
+            .collect()
+    }
+
+    fn index(&self, (row, col): (usize, usize)) -> usize {
+        assert!(row < self.height && col < self.width);
+        row * self.width + col
+    }
+
+    // グリッド内の隣接セルを列挙
+    fn neighbors(&self, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+        self.connectivity
+            .offsets()
+            .iter()
+            .filter_map(|&(dr, dc)| {
+                let r = row.checked_add_signed(dr)?;
+                let c = col.checked_add_signed(dc)?;
+                if r < self.height && c < self.width {
+                    Some((r, c))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(grid: &[&str]) -> Vec<Vec<bool>> {
+        grid.iter()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_four_connectivity() {
+        let grid = parse(&["##..#", "#..##", "..#..", "#.#.#"]);
+        let mut guf = GridUnionFind::from_grid(&grid, Connectivity::Four);
+        assert_eq!(guf.shape(), (4, 5));
+        assert_eq!(guf.get_root_count(), 5);
+        assert!(guf.same((0, 0), (1, 0)));
+        assert!(guf.same((0, 4), (1, 3)));
+        assert!(!guf.same((1, 3), (2, 2)));
+        assert!(!guf.same((0, 2), (0, 2)));
+        assert_eq!(guf.size((0, 1)), 3);
+        assert_eq!(guf.size((2, 2)), 2);
+        assert_eq!(guf.size((2, 0)), 0);
+    }
+
+    #[test]
+    fn test_eight_connectivity() {
+        let grid = parse(&["##..#", "#..##", "..#..", "#.#.#"]);
+        let mut guf = GridUnionFind::from_grid(&grid, Connectivity::Eight);
+        assert_eq!(guf.get_root_count(), 4);
+        assert!(guf.same((1, 3), (2, 2)));
+        assert!(guf.same((3, 2), (0, 4)));
+        assert!(!guf.same((3, 4), (0, 4)));
+        assert!(!guf.same((0, 0), (3, 0)));
+        assert_eq!(guf.size((2, 2)), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "gridの行の長さが揃っていません")]
+    fn test_from_ragged_grid() {
+        let grid = vec![vec![true, false], vec![true, false, true]];
+        GridUnionFind::from_grid(&grid, Connectivity::Four);
+    }
+
+    #[test]
+    fn test_activate() {
+        let mut guf = GridUnionFind::new(3, 3, Connectivity::Four);
+        assert_eq!(guf.get_root_count(), 0);
+        assert!(guf.activate((0, 0)));
+        assert!(guf.activate((0, 2)));
+        assert_eq!(guf.get_root_count(), 2);
+        assert!(!guf.activate((0, 2)));
+        // 間のセルで2つのグループが繋がる
+        assert!(guf.activate((0, 1)));
+        assert_eq!(guf.get_root_count(), 1);
+        assert_eq!(guf.size((0, 0)), 3);
+        assert!(guf.is_active((0, 1)));
+        assert!(!guf.is_active((1, 1)));
+    }
+
+    #[test]
+    fn test_components_and_render() {
+        let grid = parse(&["#.#", "#..", ".##"]);
+        let mut guf = GridUnionFind::from_grid(&grid, Connectivity::Four);
+        assert_eq!(
+            guf.components(),
+            vec![vec![(0, 0), (1, 0)], vec![(0, 2)], vec![(2, 1), (2, 2)]]
+        );
+        assert_eq!(
+            guf.component_ids(),
+            vec![
+                vec![Some(0), None, Some(1)],
+                vec![Some(0), None, None],
+                vec![None, Some(2), Some(2)]
+            ]
+        );
+        let rendered = guf.render(|id| match id {
+            Some(id) => (b'a' + id as u8) as char,
+            None => '.',
+        });
+        assert_eq!(
+            rendered,
+            vec![
+                vec!['a', '.', 'b'],
+                vec!['a', '.', '.'],
+                vec!['.', 'c', 'c']
+            ]
+        );
+    }
+}
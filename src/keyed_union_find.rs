@@ -0,0 +1,163 @@
+use crate::union_find::UnionFind;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// 任意のキーを要素とするUnionFind
+/// キーには初めて使われた順に0から連番のidを割り当てる
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K: Hash + Eq> {
+    ids: HashMap<K, usize>,
+    // id -> キー
+    keys: Vec<K>,
+    uf: UnionFind,
+}
+
+impl<K: Hash + Eq + Clone> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            keys: vec![],
+            uf: UnionFind::new(0),
+        }
+    }
+
+    /// 登録済みのキーの数を取得
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// キーが1つも登録されていないか判定
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 登録済みのキーのうちルートの数を取得
+    pub fn get_root_count(&self) -> usize {
+        self.uf.get_root_count()
+    }
+
+    /// keyのidを取得 未登録の場合はNone
+    pub fn id_of(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    /// idのキーを取得
+    pub fn key_of(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    /// keyのidを取得 未登録の場合は新たに割り当てる
+    pub fn get_or_insert_id(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.uf.add_node();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    /// key_aとkey_bそれぞれを含むグループを結合する
+    pub fn merge(&mut self, key_a: K, key_b: K) {
+        let id_a = self.get_or_insert_id(key_a);
+        let id_b = self.get_or_insert_id(key_b);
+        self.uf.merge(id_a, id_b);
+    }
+
+    /// key_aとkey_bが同一のグループか判定
+    /// 未登録のキーは自身のみのグループとして扱う
+    pub fn same(&mut self, key_a: &K, key_b: &K) -> bool {
+        match (self.id_of(key_a), self.id_of(key_b)) {
+            (Some(id_a), Some(id_b)) => self.uf.same(id_a, id_b),
+            _ => key_a == key_b,
+        }
+    }
+
+    /// keyが属するグループのsizeを取得
+    /// 未登録のキーは1
+    pub fn size(&mut self, key: &K) -> usize {
+        match self.id_of(key) {
+            Some(id) => self.uf.get_root_and_size(id).1,
+            None => 1,
+        }
+    }
+
+    /// グループごとのキーのリストを取得
+    /// グループ、グループ内ともにidの昇順
+    pub fn groups(&mut self) -> Vec<Vec<K>> {
+        let keys = &self.keys;
+        self.uf
+            .groups()
+            .into_iter()
+            .map(|group| group.into_iter().map(|id| keys[id].clone()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_keys() {
+        let mut uf = KeyedUnionFind::new();
+        uf.merge("alice".to_string(), "bob".to_string());
+        uf.merge("carol".to_string(), "dave".to_string());
+        uf.merge("bob".to_string(), "carol".to_string());
+        uf.merge("eve".to_string(), "eve".to_string());
+        assert_eq!(uf.len(), 5);
+        assert_eq!(uf.get_root_count(), 2);
+        assert!(uf.same(&"alice".to_string(), &"dave".to_string()));
+        assert!(!uf.same(&"alice".to_string(), &"eve".to_string()));
+        assert_eq!(uf.size(&"dave".to_string()), 4);
+        assert_eq!(uf.size(&"eve".to_string()), 1);
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let mut uf: KeyedUnionFind<u64> = KeyedUnionFind::new();
+        assert!(uf.is_empty());
+        assert!(uf.same(&1, &1));
+        assert!(!uf.same(&1, &2));
+        assert_eq!(uf.size(&1), 1);
+        assert_eq!(uf.id_of(&1), None);
+        // 問い合わせではidを割り当てない
+        assert!(uf.is_empty());
+    }
+
+    #[test]
+    fn test_id_of_and_key_of() {
+        let mut uf = KeyedUnionFind::new();
+        uf.merge(1_000_000_000_000_000_000u64, 7);
+        uf.merge(7, 42);
+        assert_eq!(uf.id_of(&1_000_000_000_000_000_000), Some(0));
+        assert_eq!(uf.id_of(&7), Some(1));
+        assert_eq!(uf.id_of(&42), Some(2));
+        assert_eq!(*uf.key_of(2), 42);
+        assert_eq!(uf.get_or_insert_id(7), 1);
+        assert_eq!(uf.get_or_insert_id(8), 3);
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut uf = KeyedUnionFind::new();
+        uf.merge((0, 0), (0, 1));
+        uf.merge((2, 2), (2, 3));
+        uf.merge((0, 1), (1, 1));
+        uf.get_or_insert_id((5, 5));
+        assert_eq!(
+            uf.groups(),
+            vec![
+                vec![(0, 0), (0, 1), (1, 1)],
+                vec![(2, 2), (2, 3)],
+                vec![(5, 5)]
+            ]
+        );
+    }
+}
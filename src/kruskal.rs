@@ -0,0 +1,154 @@
+use crate::union_find::UnionFind;
+use std::ops::Add;
+
+/// クラスカル法で最小全域森を求める
+/// (総コスト, 採用した辺のインデックス) を返却
+/// 非連結の場合は各連結成分の最小全域木を合わせた森になる
+///
+/// # Arguments
+/// * `n` - 頂点数
+/// * `edges` - (頂点a, 頂点b, コスト) の辺リスト
+pub fn kruskal<T>(n: usize, edges: &[(usize, usize, T)]) -> (T, Vec<usize>)
+where
+    T: Copy + Ord + Add<Output = T> + Default,
+{
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by_key(|&i| edges[i].2);
+    kruskal_in_order(n, edges, order)
+}
+
+/// クラスカル法で最大全域森を求める
+/// (総コスト, 採用した辺のインデックス) を返却
+///
+/// # Arguments
+/// * `n` - 頂点数
+/// * `edges` - (頂点a, 頂点b, コスト) の辺リスト
+pub fn kruskal_max<T>(n: usize, edges: &[(usize, usize, T)]) -> (T, Vec<usize>)
+where
+    T: Copy + Ord + Add<Output = T> + Default,
+{
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(edges[i].2));
+    kruskal_in_order(n, edges, order)
+}
+
+/// 最小全域森でk番目(0-indexed)に採用される辺のインデックスを返却
+/// 採用される辺がk本以下の場合はNone
+///
+/// # Arguments
+/// * `n` - 頂点数
+/// * `edges` - (頂点a, 頂点b, コスト) の辺リスト
+/// * `k` - 採用順 (コストの昇順)
+pub fn kruskal_kth_edge<T>(n: usize, edges: &[(usize, usize, T)], k: usize) -> Option<usize>
+where
+    T: Copy + Ord + Add<Output = T> + Default,
+{
+    kruskal(n, edges).1.get(k).copied()
+}
+
+/// edgesのうちsubsetに含まれる辺のみを使って最小全域森を求める
+/// (総コスト, 採用した辺のインデックス) を返却 インデックスはedges上のもの
+///
+/// # Arguments
+/// * `n` - 頂点数
+/// * `edges` - (頂点a, 頂点b, コスト) の辺リスト
+/// * `subset` - 使用してよい辺のインデックス
+pub fn kruskal_subset<T>(n: usize, edges: &[(usize, usize, T)], subset: &[usize]) -> (T, Vec<usize>)
+where
+    T: Copy + Ord + Add<Output = T> + Default,
+{
+    let mut order: Vec<usize> = subset.to_vec();
+    order.sort_by_key(|&i| edges[i].2);
+    kruskal_in_order(n, edges, order)
+}
+
+// orderの順に辺を見て、閉路を作らないものを採用する
+fn kruskal_in_order<T>(n: usize, edges: &[(usize, usize, T)], order: Vec<usize>) -> (T, Vec<usize>)
+where
+    T: Copy + Ord + Add<Output = T> + Default,
+{
+    let mut uf = UnionFind::new(n);
+    let mut cost = T::default();
+    let mut used = vec![];
+    for i in order {
+        // 全域木が完成したら以降の辺は不要
+        if uf.get_root_count() <= 1 {
+            break;
+        }
+        let (a, b, c) = edges[i];
+        if uf.merge(a, b).is_some() {
+            cost = cost + c;
+            used.push(i);
+        }
+    }
+    (cost, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_edges() -> Vec<(usize, usize, i64)> {
+        vec![
+            (0, 1, 4),
+            (0, 2, 1),
+            (1, 2, 2),
+            (1, 3, 5),
+            (2, 3, 8),
+            (3, 4, 3),
+            (2, 4, 9),
+        ]
+    }
+
+    #[test]
+    fn test_kruskal() {
+        let edges = sample_edges();
+        let (cost, used) = kruskal(5, &edges);
+        assert_eq!(cost, 11);
+        assert_eq!(used, vec![1, 2, 5, 3]);
+    }
+
+    #[test]
+    fn test_kruskal_forest() {
+        // {0, 1, 2} と {3, 4} と {5} の森
+        let edges: Vec<(usize, usize, i64)> = vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (3, 4, 7)];
+        let (cost, used) = kruskal(6, &edges);
+        assert_eq!(cost, 10);
+        assert_eq!(used, vec![1, 2, 3]);
+        // 連結成分数 = 頂点数 - 採用辺数
+        assert_eq!(6 - used.len(), 3);
+
+        let (cost, used) = kruskal::<i64>(3, &[]);
+        assert_eq!(cost, 0);
+        assert!(used.is_empty());
+    }
+
+    #[test]
+    fn test_kruskal_max() {
+        let edges = sample_edges();
+        let (cost, used) = kruskal_max(5, &edges);
+        assert_eq!(cost, 26);
+        assert_eq!(used, vec![6, 4, 3, 0]);
+    }
+
+    #[test]
+    fn test_kruskal_kth_edge() {
+        let edges = sample_edges();
+        assert_eq!(kruskal_kth_edge(5, &edges, 0), Some(1));
+        assert_eq!(kruskal_kth_edge(5, &edges, 3), Some(3));
+        assert_eq!(kruskal_kth_edge(5, &edges, 4), None);
+    }
+
+    #[test]
+    fn test_kruskal_subset() {
+        let edges = sample_edges();
+        let (cost, used) = kruskal_subset(5, &edges, &[0, 2, 4, 6]);
+        assert_eq!(cost, 23);
+        assert_eq!(used, vec![2, 0, 4, 6]);
+
+        // 連結にならない部分集合
+        let (cost, used) = kruskal_subset(5, &edges, &[1, 5]);
+        assert_eq!(cost, 4);
+        assert_eq!(used, vec![1, 5]);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::union_find::UnionFind;
+
+/// クラスカル法の結合過程を木にしたもの (クラスカル再構築木)
+/// node 0..nが元の頂点(葉)、n以降が結合ごとに作られる内部node
+/// 内部nodeは結合に使った辺の重みを持ち、根に向かって重みは単調非減少
+/// 連結なら2n-1個のnodeからなる木、非連結なら連結成分ごとの森になる
+#[derive(Debug, Clone)]
+pub struct KruskalTree<T: Copy + Ord> {
+    n: usize,
+    // 親node 根の場合は自身
+    parent: Vec<usize>,
+    // 内部nodeの重み 葉の場合はNone
+    weight: Vec<Option<T>>,
+    // 部分木に含まれる葉の数
+    leaf_count: Vec<usize>,
+    depth: Vec<usize>,
+    // ancestors[k][node] -> nodeの2^k個上の祖先 (根より上は根)
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<T: Copy + Ord> KruskalTree<T> {
+    /// 頂点数nと (頂点a, 頂点b, 重み) の辺リストから作成
+    pub fn new(n: usize, edges: &[(usize, usize, T)]) -> Self {
+        let mut sorted: Vec<&(usize, usize, T)> = edges.iter().collect();
+        sorted.sort_by_key(|&&(_, _, w)| w);
+
+        let mut parent: Vec<usize> = (0..n).collect();
+        let mut weight: Vec<Option<T>> = vec![None; n];
+        let mut leaf_count = vec![1; n];
+        let mut uf = UnionFind::new(n);
+        // UnionFindのroot_node -> そのグループに対応する木のnode
+        let mut group_node: Vec<usize> = (0..n).collect();
+        for &&(a, b, w) in &sorted {
+            let (new_root, absorbed_root) = match uf.merge(a, b) {
+                Some(roots) => roots,
+                None => continue,
+            };
+            let new_node = parent.len();
+            let (node_a, node_b) = (group_node[new_root], group_node[absorbed_root]);
+            parent[node_a] = new_node;
+            parent[node_b] = new_node;
+            parent.push(new_node);
+            weight.push(Some(w));
+            leaf_count.push(leaf_count[node_a] + leaf_count[node_b]);
+            group_node[new_root] = new_node;
+        }
+
+        // 親のnode番号は子より大きいので、番号の大きい順に深さが決まる
+        let node_count = parent.len();
+        let mut depth = vec![0; node_count];
+        for node in (0..node_count).rev() {
+            if parent[node] != node {
+                depth[node] = depth[parent[node]] + 1;
+            }
+        }
+        let mut ancestors = vec![parent.clone()];
+        while 1 << ancestors.len() < node_count {
+            let last = ancestors.last().unwrap();
+            let next = (0..node_count).map(|node| last[last[node]]).collect();
+            ancestors.push(next);
+        }
+
+        Self {
+            n,
+            parent,
+            weight,
+            leaf_count,
+            depth,
+            ancestors,
+        }
+    }
+
+    /// 木全体のnode数を取得
+    pub fn node_count(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// nodeが元の頂点(葉)か判定
+    pub fn is_leaf(&self, node: usize) -> bool {
+        node < self.n
+    }
+
+    /// nodeの親を取得 根の場合はNone
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        if self.parent[node] == node {
+            None
+        } else {
+            Some(self.parent[node])
+        }
+    }
+
+    /// 内部nodeの重み(結合に使った辺の重み)を取得 葉の場合はNone
+    pub fn weight(&self, node: usize) -> Option<T> {
+        self.weight[node]
+    }
+
+    /// nodeの部分木に含まれる元の頂点の数を取得
+    pub fn subtree_size(&self, node: usize) -> usize {
+        self.leaf_count[node]
+    }
+
+    /// node_aとnode_bの最小共通祖先を取得 別の木に属する場合はNone
+    pub fn lca(&self, node_a: usize, node_b: usize) -> Option<usize> {
+        let (mut a, mut b) = if self.depth[node_a] >= self.depth[node_b] {
+            (node_a, node_b)
+        } else {
+            (node_b, node_a)
+        };
+        let diff = self.depth[a] - self.depth[b];
+        for (k, ancestor) in self.ancestors.iter().enumerate() {
+            if diff >> k & 1 == 1 {
+                a = ancestor[a];
+            }
+        }
+        if a == b {
+            return Some(a);
+        }
+        for ancestor in self.ancestors.iter().rev() {
+            if ancestor[a] != ancestor[b] {
+                a = ancestor[a];
+                b = ancestor[b];
+            }
+        }
+        if self.parent[a] == self.parent[b] && self.parent[a] != a {
+            Some(self.parent[a])
+        } else {
+            None
+        }
+    }
+
+    /// 頂点uから頂点vへの経路で通る辺の重みの最大値の最小値を取得
+    /// u == vの場合や、非連結の場合はNone
+    pub fn bottleneck(&self, u: usize, v: usize) -> Option<T> {
+        self.lca(u, v).and_then(|node| self.weight[node])
+    }
+
+    /// nodeの祖先(node自身を含む)のうち、重みがlimit以下で最も根に近いnodeを取得
+    pub fn highest_ancestor_within(&self, node: usize, limit: T) -> usize {
+        let mut node = node;
+        for ancestor in self.ancestors.iter().rev() {
+            let next = ancestor[node];
+            if next != node && self.weight[next].is_some_and(|w| w <= limit) {
+                node = next;
+            }
+        }
+        node
+    }
+
+    /// 頂点uから重みlimit以下の辺のみを使って到達できる頂点の数を取得 (u自身を含む)
+    pub fn reachable_count(&self, u: usize, limit: T) -> usize {
+        self.leaf_count[self.highest_ancestor_within(u, limit)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 同じ重みの辺が複数あり、どちらも結合に使われる
+    fn tied_edges() -> Vec<(usize, usize, i64)> {
+        vec![
+            (0, 1, 2),
+            (2, 3, 2),
+            (1, 2, 5),
+            (3, 4, 5),
+            (4, 5, 1),
+            (0, 5, 9),
+            (1, 3, 5),
+        ]
+    }
+
+    #[test]
+    fn test_structure() {
+        let tree = KruskalTree::new(6, &tied_edges());
+        assert_eq!(tree.node_count(), 11);
+        // 重み1で{4, 5}
+        assert_eq!(tree.parent(4), Some(6));
+        assert_eq!(tree.weight(6), Some(1));
+        // 重み2の2本の辺はどちらも結合し、別々の内部nodeになる
+        assert_eq!(tree.parent(0), Some(7));
+        assert_eq!(tree.parent(1), Some(7));
+        assert_eq!(tree.parent(2), Some(8));
+        assert_eq!(tree.parent(3), Some(8));
+        assert_eq!(tree.weight(7), Some(2));
+        assert_eq!(tree.weight(8), Some(2));
+        // 重み5の辺で{0, 1, 2, 3}、続けて全体 (1-3の辺は不要)
+        assert_eq!(tree.parent(7), Some(9));
+        assert_eq!(tree.parent(8), Some(9));
+        assert_eq!(tree.parent(9), Some(10));
+        assert_eq!(tree.parent(6), Some(10));
+        assert_eq!(tree.weight(9), Some(5));
+        assert_eq!(tree.weight(10), Some(5));
+        assert_eq!(tree.parent(10), None);
+        assert_eq!(tree.weight(3), None);
+        assert!(tree.is_leaf(5));
+        assert!(!tree.is_leaf(6));
+        assert_eq!(tree.subtree_size(9), 4);
+        assert_eq!(tree.subtree_size(10), 6);
+    }
+
+    #[test]
+    fn test_bottleneck() {
+        let tree = KruskalTree::new(6, &tied_edges());
+        assert_eq!(tree.bottleneck(0, 1), Some(2));
+        assert_eq!(tree.bottleneck(2, 3), Some(2));
+        assert_eq!(tree.bottleneck(0, 3), Some(5));
+        assert_eq!(tree.bottleneck(5, 4), Some(1));
+        assert_eq!(tree.bottleneck(0, 5), Some(5));
+        assert_eq!(tree.bottleneck(2, 2), None);
+        assert_eq!(tree.lca(1, 1), Some(1));
+        assert_eq!(tree.lca(0, 4), Some(10));
+    }
+
+    #[test]
+    fn test_reachable_count() {
+        let tree = KruskalTree::new(6, &tied_edges());
+        assert_eq!(tree.reachable_count(0, 1), 1);
+        assert_eq!(tree.reachable_count(0, 2), 2);
+        assert_eq!(tree.reachable_count(0, 4), 2);
+        assert_eq!(tree.reachable_count(4, 4), 2);
+        // 同じ重みの内部nodeが続いても最上位まで上る
+        assert_eq!(tree.highest_ancestor_within(0, 5), 10);
+        assert_eq!(tree.reachable_count(2, 5), 6);
+        assert_eq!(tree.reachable_count(5, 100), 6);
+    }
+
+    #[test]
+    fn test_forest() {
+        let edges: Vec<(usize, usize, i64)> = vec![(0, 1, 3), (2, 3, 1)];
+        let tree = KruskalTree::new(5, &edges);
+        assert_eq!(tree.node_count(), 7);
+        assert_eq!(tree.bottleneck(0, 1), Some(3));
+        assert_eq!(tree.bottleneck(1, 2), None);
+        assert_eq!(tree.lca(4, 0), None);
+        assert_eq!(tree.reachable_count(2, 10), 2);
+    }
+
+    #[test]
+    fn test_compare_with_minimax_distance() {
+        // 重みが3種類しかなく同じ重みの辺が多いグラフで、
+        // ワーシャルフロイド風に求めた「経路上の最大重みの最小値」と比較する
+        let n = 12;
+        let mut edges: Vec<(usize, usize, i64)> = vec![];
+        for a in 0..n {
+            for b in a + 1..n {
+                if (a * 5 + b * 3) % 7 == 0 {
+                    edges.push((a, b, ((a + b) % 3) as i64));
+                }
+            }
+        }
+        let tree = KruskalTree::new(n, &edges);
+
+        let mut minimax: Vec<Vec<Option<i64>>> = vec![vec![None; n]; n];
+        for &(a, b, w) in &edges {
+            minimax[a][b] = Some(minimax[a][b].map_or(w, |x| x.min(w)));
+            minimax[b][a] = minimax[a][b];
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if let (Some(x), Some(y)) = (minimax[i][k], minimax[k][j]) {
+                        let via = x.max(y);
+                        match minimax[i][j] {
+                            Some(w) if w <= via => {}
+                            _ => minimax[i][j] = Some(via),
+                        }
+                    }
+                }
+            }
+        }
+
+        for (u, row) in minimax.iter().enumerate() {
+            for (v, &w) in row.iter().enumerate() {
+                if u != v {
+                    assert_eq!(tree.bottleneck(u, v), w);
+                }
+            }
+            for limit in 0..3 {
+                let expected = (0..n)
+                    .filter(|&v| v == u || row[v].is_some_and(|w| w <= limit))
+                    .count();
+                assert_eq!(tree.reachable_count(u, limit), expected);
+            }
+        }
+    }
+}
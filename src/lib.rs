@@ -1,8 +1,30 @@
+pub mod concurrent_union_find;
+pub mod deletable_union_find;
+pub mod grid_union_find;
+pub mod keyed_union_find;
+pub mod kruskal;
+pub mod kruskal_tree;
+pub mod parity_union_find;
+pub mod partially_persistent_union_find;
+pub mod persistent_array;
+pub mod persistent_union_find;
 pub mod segment_tree;
+pub mod tree;
 pub mod union_find;
 pub mod utils;
 pub mod weighted_union_find;
+pub use concurrent_union_find::*;
+pub use deletable_union_find::*;
+pub use grid_union_find::*;
+pub use keyed_union_find::*;
+pub use kruskal::*;
+pub use kruskal_tree::*;
+pub use parity_union_find::*;
+pub use partially_persistent_union_find::*;
+pub use persistent_array::*;
+pub use persistent_union_find::*;
 pub use segment_tree::*;
+pub use tree::*;
 pub use union_find::*;
 pub use utils::*;
 pub use weighted_union_find::*;
@@ -0,0 +1,238 @@
+#[derive(Debug, Clone, Copy)]
+enum ParityUnionFindNode {
+    ParentAndParity(usize, bool), //親ノード番号と親と色が異なるか
+    Root(usize, usize, bool),     //rootと同色のnode数、異色のnode数、二部グラフか
+}
+
+/// 2色の塗り分けを管理するUnionFind
+/// 同一グループのnode同士は同色か異色かが定まる
+#[derive(Debug, Clone)]
+pub struct ParityUnionFind {
+    // ルートノードであるとき->色ごとのnode数と二部グラフか
+    // ルートノードでないとき->親と親と色が異なるか
+    graph: Vec<ParityUnionFindNode>,
+    root_count: usize,
+    bipartite: bool,
+}
+
+impl ParityUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            graph: vec![ParityUnionFindNode::Root(1, 0, true); size],
+            root_count: size,
+            bipartite: true,
+        }
+    }
+
+    /// ルートの数を取得
+    pub fn get_root_count(&self) -> usize {
+        self.root_count
+    }
+
+    /// node_aとnode_bを同色として結合する
+    /// false: 既結合かつ異色の時 (奇閉路ができた時)
+    pub fn merge_same(&mut self, node_a: usize, node_b: usize) -> bool {
+        self.merge(node_a, node_b, false)
+    }
+
+    /// node_aとnode_bを異色として結合する
+    /// false: 既結合かつ同色の時 (奇閉路ができた時)
+    pub fn merge_diff(&mut self, node_a: usize, node_b: usize) -> bool {
+        self.merge(node_a, node_b, true)
+    }
+
+    /// これまでの全ての制約を満たす2色の塗り分けが存在するか判定
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartite
+    }
+
+    /// nodeが属するグループが2色に塗り分けられるか判定
+    pub fn is_component_bipartite(&mut self, node: usize) -> bool {
+        let root = self.get_root_and_parity(node).0;
+        match self.graph[root] {
+            ParityUnionFindNode::Root(_, _, bipartite) => bipartite,
+            _ => unreachable!(),
+        }
+    }
+
+    /// node_aとnode_bが同一のグループか判定
+    pub fn same(&mut self, node_a: usize, node_b: usize) -> bool {
+        self.get_root_and_parity(node_a).0 == self.get_root_and_parity(node_b).0
+    }
+
+    /// node_aとnode_bが同色か判定
+    /// 別グループの場合はNone
+    pub fn same_color(&mut self, node_a: usize, node_b: usize) -> Option<bool> {
+        let (root_a, parity_a) = self.get_root_and_parity(node_a);
+        let (root_b, parity_b) = self.get_root_and_parity(node_b);
+        if root_a == root_b {
+            Some(parity_a == parity_b)
+        } else {
+            None
+        }
+    }
+
+    /// nodeが属するグループの (nodeと同色のnode数, 異色のnode数) を取得
+    pub fn color_counts(&mut self, node: usize) -> (usize, usize) {
+        let (root, parity) = self.get_root_and_parity(node);
+        match self.graph[root] {
+            ParityUnionFindNode::Root(same, diff, _) => {
+                if parity {
+                    (diff, same)
+                } else {
+                    (same, diff)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// 全グループの (rootと同色のnode数, 異色のnode数) を取得
+    /// グループごとにどちらの色を選ぶかのDPにそのまま使える
+    pub fn component_color_counts(&self) -> Vec<(usize, usize)> {
+        self.graph
+            .iter()
+            .filter_map(|node| match *node {
+                ParityUnionFindNode::Root(same, diff, _) => Some((same, diff)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// nodeのroot_nodeと、nodeがrootと異色かを取得
+    /// nodeの親をroot_nodeに再設定 (経路圧縮)
+    pub fn get_root_and_parity(&mut self, node: usize) -> (usize, bool) {
+        let mut node_tmp: usize = node;
+        let mut parity_tmp: bool = false;
+        loop {
+            match self.graph[node_tmp] {
+                ParityUnionFindNode::ParentAndParity(parent, parity) => {
+                    parity_tmp ^= parity;
+                    node_tmp = parent;
+                }
+                ParityUnionFindNode::Root(..) => {
+                    if node != node_tmp {
+                        self.graph[node] =
+                            ParityUnionFindNode::ParentAndParity(node_tmp, parity_tmp);
+                    }
+                    return (node_tmp, parity_tmp);
+                }
+            }
+        }
+    }
+
+    // node_aとnode_bを、differentがtrueなら異色、falseなら同色として結合する
+    fn merge(&mut self, node_a: usize, node_b: usize, different: bool) -> bool {
+        let (root_a, parity_a) = self.get_root_and_parity(node_a);
+        let (root_b, parity_b) = self.get_root_and_parity(node_b);
+        let (same_a, diff_a, bipartite_a) = match self.graph[root_a] {
+            ParityUnionFindNode::Root(same, diff, bipartite) => (same, diff, bipartite),
+            _ => unreachable!(),
+        };
+
+        if root_a == root_b {
+            if (parity_a ^ parity_b) == different {
+                true
+            } else {
+                self.graph[root_a] = ParityUnionFindNode::Root(same_a, diff_a, false);
+                self.bipartite = false;
+                false
+            }
+        } else {
+            let (same_b, diff_b, bipartite_b) = match self.graph[root_b] {
+                ParityUnionFindNode::Root(same, diff, bipartite) => (same, diff, bipartite),
+                _ => unreachable!(),
+            };
+            self.root_count -= 1;
+            // root_aとroot_bが異色か
+            let parity_root = parity_a ^ parity_b ^ different;
+            let (root_bigger, root_smaller, same_bigger, diff_bigger, same_smaller, diff_smaller) =
+                if same_a + diff_a >= same_b + diff_b {
+                    (root_a, root_b, same_a, diff_a, same_b, diff_b)
+                } else {
+                    (root_b, root_a, same_b, diff_b, same_a, diff_a)
+                };
+            let (same, diff) = if parity_root {
+                (same_bigger + diff_smaller, diff_bigger + same_smaller)
+            } else {
+                (same_bigger + same_smaller, diff_bigger + diff_smaller)
+            };
+            self.graph[root_smaller] =
+                ParityUnionFindNode::ParentAndParity(root_bigger, parity_root);
+            self.graph[root_bigger] =
+                ParityUnionFindNode::Root(same, diff, bipartite_a && bipartite_b);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let uf = ParityUnionFind::new(5);
+        assert_eq!(uf.get_root_count(), 5);
+        assert!(uf.is_bipartite());
+    }
+
+    #[test]
+    fn test_merge_same_and_diff() {
+        let mut uf = ParityUnionFind::new(5);
+        assert!(uf.merge_diff(0, 1));
+        assert!(uf.merge_diff(1, 2));
+        assert!(uf.merge_same(3, 4));
+        assert_eq!(uf.get_root_count(), 2);
+        assert_eq!(uf.same_color(0, 2), Some(true));
+        assert_eq!(uf.same_color(0, 1), Some(false));
+        assert_eq!(uf.same_color(3, 4), Some(true));
+        assert_eq!(uf.same_color(0, 3), None);
+        // 既に同じ関係にある制約は矛盾しない
+        assert!(uf.merge_same(2, 0));
+        assert!(uf.merge_diff(2, 1));
+        assert!(uf.is_bipartite());
+    }
+
+    #[test]
+    fn test_odd_cycle() {
+        let mut uf = ParityUnionFind::new(6);
+        // 0-1-2-3-0 の偶閉路
+        assert!(uf.merge_diff(0, 1));
+        assert!(uf.merge_diff(1, 2));
+        assert!(uf.merge_diff(2, 3));
+        assert!(uf.merge_diff(3, 0));
+        assert!(uf.is_bipartite());
+        // 4-5-4 は問題ないが 0-2 を結ぶと奇閉路
+        assert!(uf.merge_diff(4, 5));
+        assert!(!uf.merge_diff(0, 2));
+        assert!(!uf.is_bipartite());
+        assert!(!uf.is_component_bipartite(1));
+        assert!(uf.is_component_bipartite(4));
+        // 奇閉路を含むグループと結合したグループも二部グラフではない
+        assert!(uf.merge_diff(5, 3));
+        assert!(!uf.is_component_bipartite(4));
+    }
+
+    #[test]
+    fn test_color_counts() {
+        let mut uf = ParityUnionFind::new(7);
+        uf.merge_diff(0, 1);
+        uf.merge_diff(0, 2);
+        uf.merge_diff(0, 3);
+        uf.merge_diff(4, 5);
+        uf.merge_diff(5, 1);
+        // {0, 5} と {1, 2, 3, 4} で塗り分けられる
+        assert_eq!(uf.color_counts(0), (2, 4));
+        assert_eq!(uf.color_counts(3), (4, 2));
+        assert_eq!(uf.color_counts(6), (1, 0));
+
+        // 各グループでどちらかの色を選び、選んだnode数の最大値を求める
+        let counts = uf.component_color_counts();
+        assert_eq!(counts.len(), 2);
+        let max_total: usize = counts.iter().map(|&(same, diff)| same.max(diff)).sum();
+        assert_eq!(max_total, 5);
+        let total: usize = counts.iter().map(|&(same, diff)| same + diff).sum();
+        assert_eq!(total, 7);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::union_find::UnionFindNode;
+use std::mem::swap;
+
+/// 部分永続UnionFind
+/// mergeを呼ぶたびに時刻が1進み、過去の任意の時刻の状態に問い合わせができる (結合は最新の時刻のみ)
+/// 経路圧縮を行わずunion by sizeのみで木の高さをO(log n)に抑える
+#[derive(Debug, Clone)]
+pub struct PartiallyPersistentUnionFind {
+    // ルートノードであるとき->最新のグループのサイズ
+    // ルートノードでないとき->親
+    graph: Vec<UnionFindNode>,
+    // ルートでなくなった時刻 ルートの場合はusize::MAX
+    time: Vec<usize>,
+    // ルートだった間の (時刻, グループのサイズ) の履歴
+    size_history: Vec<Vec<(usize, usize)>>,
+    // 結合が起きた時刻の一覧
+    merge_times: Vec<usize>,
+    now: usize,
+}
+
+impl PartiallyPersistentUnionFind {
+    /// 全nodeが別グループの状態を時刻0として作成
+    pub fn new(size: usize) -> Self {
+        Self {
+            graph: vec![UnionFindNode::Size(1); size],
+            time: vec![usize::MAX; size],
+            size_history: vec![vec![(0, 1)]; size],
+            merge_times: vec![],
+            now: 0,
+        }
+    }
+
+    /// 最新の時刻 (これまでにmergeを呼んだ回数) を取得
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    /// 時刻tでのルートの数を取得
+    pub fn get_root_count_at(&self, t: usize) -> usize {
+        self.graph.len() - self.merge_times.partition_point(|&time| time <= t)
+    }
+
+    /// 時刻を1進め、node_aとnode_bそれぞれを含むグループを結合する
+    /// true: 結合した時
+    /// false: 既に同一グループだった時
+    pub fn merge(&mut self, node_a: usize, node_b: usize) -> bool {
+        self.now += 1;
+        let root_a = self.find_at(self.now, node_a);
+        let root_b = self.find_at(self.now, node_b);
+        if root_a == root_b {
+            return false;
+        }
+        let (size_a, size_b) = match (self.graph[root_a], self.graph[root_b]) {
+            (UnionFindNode::Size(size_a), UnionFindNode::Size(size_b)) => (size_a, size_b),
+            _ => unreachable!(),
+        };
+        let (root_bigger, root_smaller) = if size_a >= size_b {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.graph[root_smaller] = UnionFindNode::Parent(root_bigger);
+        self.graph[root_bigger] = UnionFindNode::Size(size_a + size_b);
+        self.time[root_smaller] = self.now;
+        self.size_history[root_bigger].push((self.now, size_a + size_b));
+        self.merge_times.push(self.now);
+        true
+    }
+
+    /// 時刻t (t回目のmergeの直後) でのnodeのroot_nodeを取得
+    pub fn find_at(&self, t: usize, node: usize) -> usize {
+        let mut node_tmp: usize = node;
+        while let UnionFindNode::Parent(parent) = self.graph[node_tmp] {
+            if self.time[node_tmp] > t {
+                break;
+            }
+            node_tmp = parent;
+        }
+        node_tmp
+    }
+
+    /// 時刻tでnode_aとnode_bが同一のグループか判定
+    pub fn same_at(&self, t: usize, node_a: usize, node_b: usize) -> bool {
+        self.find_at(t, node_a) == self.find_at(t, node_b)
+    }
+
+    /// 時刻tでnodeが属するグループのsizeを取得
+    pub fn size_at(&self, t: usize, node: usize) -> usize {
+        let history = &self.size_history[self.find_at(t, node)];
+        let i = history.partition_point(|&(time, _)| time <= t);
+        history[i - 1].1
+    }
+
+    /// node_aとnode_bが同一のグループになった最初の時刻を取得
+    /// 最新の時刻でも別グループの場合はNone
+    /// 親への辺が早く張られた方から根へ上っていき、合流するまでに通った辺の時刻の最大値を求める O(log n)
+    pub fn connected_time(&self, node_a: usize, node_b: usize) -> Option<usize> {
+        if !self.same_at(self.now, node_a, node_b) {
+            return None;
+        }
+        let (mut node_a, mut node_b) = (node_a, node_b);
+        let mut connected = 0;
+        while node_a != node_b {
+            if self.time[node_a] > self.time[node_b] {
+                swap(&mut node_a, &mut node_b);
+            }
+            connected = self.time[node_a];
+            node_a = match self.graph[node_a] {
+                UnionFindNode::Parent(parent) => parent,
+                UnionFindNode::Size(_) => unreachable!(),
+            };
+        }
+        Some(connected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::union_find::UnionFind;
+
+    #[test]
+    fn test_same_and_size_at() {
+        let mut uf = PartiallyPersistentUnionFind::new(5);
+        assert!(uf.merge(0, 1));
+        assert!(uf.merge(2, 3));
+        assert!(!uf.merge(1, 0));
+        assert!(uf.merge(1, 3));
+        assert_eq!(uf.now(), 4);
+
+        assert!(!uf.same_at(0, 0, 1));
+        assert!(uf.same_at(1, 0, 1));
+        assert!(!uf.same_at(3, 0, 2));
+        assert!(uf.same_at(4, 0, 2));
+        assert!(!uf.same_at(4, 0, 4));
+
+        assert_eq!(uf.size_at(0, 3), 1);
+        assert_eq!(uf.size_at(2, 3), 2);
+        assert_eq!(uf.size_at(3, 0), 2);
+        assert_eq!(uf.size_at(4, 0), 4);
+        assert_eq!(uf.size_at(100, 4), 1);
+
+        assert_eq!(uf.get_root_count_at(0), 5);
+        assert_eq!(uf.get_root_count_at(3), 3);
+        assert_eq!(uf.get_root_count_at(4), 2);
+    }
+
+    #[test]
+    fn test_connected_time() {
+        let mut uf = PartiallyPersistentUnionFind::new(6);
+        uf.merge(0, 1);
+        uf.merge(2, 3);
+        uf.merge(4, 5);
+        uf.merge(3, 0);
+        uf.merge(1, 2);
+        assert_eq!(uf.connected_time(0, 1), Some(1));
+        assert_eq!(uf.connected_time(2, 3), Some(2));
+        assert_eq!(uf.connected_time(1, 2), Some(4));
+        assert_eq!(uf.connected_time(3, 3), Some(0));
+        assert_eq!(uf.connected_time(0, 4), None);
+        uf.merge(5, 2);
+        assert_eq!(uf.connected_time(0, 4), Some(6));
+    }
+
+    #[test]
+    fn test_compare_with_union_find() {
+        let n = 25;
+        let edges: Vec<(usize, usize)> =
+            (0..40).map(|i| ((i * 11) % n, (i * 17 + 3) % n)).collect();
+        let mut ppuf = PartiallyPersistentUnionFind::new(n);
+        let mut uf = UnionFind::new(n);
+        let mut snapshots = vec![uf.clone()];
+        for &(a, b) in &edges {
+            ppuf.merge(a, b);
+            uf.merge(a, b);
+            snapshots.push(uf.clone());
+        }
+        for (t, snapshot) in snapshots.iter_mut().enumerate() {
+            for a in 0..n {
+                for b in 0..n {
+                    assert_eq!(ppuf.same_at(t, a, b), snapshot.same(a, b));
+                }
+                assert_eq!(ppuf.size_at(t, a), snapshot.get_root_and_size(a).1);
+            }
+            assert_eq!(ppuf.get_root_count_at(t), snapshot.get_root_count());
+        }
+        for a in 0..n {
+            for b in 0..n {
+                let expected = (0..snapshots.len()).find(|&t| snapshots[t].same(a, b));
+                assert_eq!(ppuf.connected_time(a, b), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+#[derive(Debug, Clone)]
+enum PersistentArrayNode<T: Clone> {
+    Leaf(T),
+    Branch(usize, usize), //左の子と右の子のノード番号
+}
+
+/// 完全永続配列
+/// setのたびに根から葉までをコピーした新しいバージョンを作る
+/// get, setともにO(log n)
+#[derive(Debug, Clone)]
+pub struct PersistentArray<T: Clone> {
+    nodes: Vec<PersistentArrayNode<T>>,
+    // バージョン -> 根のノード番号
+    versions: Vec<usize>,
+    len: usize,
+}
+
+impl<T: Clone> PersistentArray<T> {
+    /// initを初期状態(バージョン0)として作成
+    pub fn new(init: Vec<T>) -> Self {
+        let len = init.len();
+        let mut array = Self {
+            nodes: vec![],
+            versions: vec![],
+            len,
+        };
+        let root = if len == 0 {
+            usize::MAX
+        } else {
+            array.build(&init, 0, len)
+        };
+        array.versions.push(root);
+        array
+    }
+
+    /// 配列の長さを取得
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 配列が空か判定
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// これまでに作られたバージョンの数を取得
+    pub fn version_count(&self) -> usize {
+        self.versions.len()
+    }
+
+    /// versionのindex番目の値を取得
+    pub fn get(&self, version: usize, index: usize) -> &T {
+        assert!(index < self.len);
+        let mut node = self.versions[version];
+        let (mut l, mut r) = (0, self.len);
+        loop {
+            match &self.nodes[node] {
+                PersistentArrayNode::Leaf(v) => return v,
+                &PersistentArrayNode::Branch(left, right) => {
+                    let mid = (l + r) / 2;
+                    if index < mid {
+                        node = left;
+                        r = mid;
+                    } else {
+                        node = right;
+                        l = mid;
+                    }
+                }
+            }
+        }
+    }
+
+    /// versionのindex番目をvalueに変更した新しいバージョンを作成し、そのバージョンを返却
+    /// versionは変更されない
+    pub fn set(&mut self, version: usize, index: usize, value: T) -> usize {
+        assert!(index < self.len);
+        let root = self.set_node(self.versions[version], 0, self.len, index, value);
+        self.versions.push(root);
+        self.versions.len() - 1
+    }
+
+    // 区間[l, r)を担当するノードを作成
+    fn build(&mut self, init: &[T], l: usize, r: usize) -> usize {
+        let node = if r - l == 1 {
+            PersistentArrayNode::Leaf(init[l].clone())
+        } else {
+            let mid = (l + r) / 2;
+            let left = self.build(init, l, mid);
+            let right = self.build(init, mid, r);
+            PersistentArrayNode::Branch(left, right)
+        };
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    // 区間[l, r)を担当するnodeをコピーしてindex番目を変更したノードを作成
+    fn set_node(&mut self, node: usize, l: usize, r: usize, index: usize, value: T) -> usize {
+        let new_node = match self.nodes[node] {
+            PersistentArrayNode::Leaf(_) => PersistentArrayNode::Leaf(value),
+            PersistentArrayNode::Branch(left, right) => {
+                let mid = (l + r) / 2;
+                if index < mid {
+                    PersistentArrayNode::Branch(self.set_node(left, l, mid, index, value), right)
+                } else {
+                    PersistentArrayNode::Branch(left, self.set_node(right, mid, r, index, value))
+                }
+            }
+        };
+        self.nodes.push(new_node);
+        self.nodes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set() {
+        let mut array = PersistentArray::new(vec![1, 2, 3, 4, 5]);
+        let v1 = array.set(0, 2, 30);
+        let v2 = array.set(v1, 4, 50);
+        let v3 = array.set(0, 0, 10);
+        assert_eq!(
+            (0..5).map(|i| *array.get(0, i)).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            (0..5).map(|i| *array.get(v1, i)).collect::<Vec<_>>(),
+            vec![1, 2, 30, 4, 5]
+        );
+        assert_eq!(
+            (0..5).map(|i| *array.get(v2, i)).collect::<Vec<_>>(),
+            vec![1, 2, 30, 4, 50]
+        );
+        assert_eq!(
+            (0..5).map(|i| *array.get(v3, i)).collect::<Vec<_>>(),
+            vec![10, 2, 3, 4, 5]
+        );
+        assert_eq!(array.version_count(), 4);
+        assert_eq!(array.len(), 5);
+    }
+
+    #[test]
+    fn test_single_and_empty() {
+        let mut array = PersistentArray::new(vec!['a']);
+        let v1 = array.set(0, 0, 'b');
+        assert_eq!(*array.get(0, 0), 'a');
+        assert_eq!(*array.get(v1, 0), 'b');
+
+        let array: PersistentArray<i32> = PersistentArray::new(vec![]);
+        assert!(array.is_empty());
+        assert_eq!(array.version_count(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::persistent_array::PersistentArray;
+use crate::union_find::UnionFindNode;
+
+/// 完全永続UnionFind
+/// mergeのたびに新しいバージョンを作り、過去の任意のバージョンに対して問い合わせや結合ができる
+/// 経路圧縮を行わずunion by sizeのみで木の高さをO(log n)に抑えるため、各操作はO(log^2 n)
+#[derive(Debug, Clone)]
+pub struct PersistentUnionFind {
+    array: PersistentArray<UnionFindNode>,
+    // バージョン -> (arrayのバージョン, ルートの数)
+    versions: Vec<(usize, usize)>,
+}
+
+impl PersistentUnionFind {
+    /// 全nodeが別グループの状態をバージョン0として作成
+    pub fn new(size: usize) -> Self {
+        Self {
+            array: PersistentArray::new(vec![UnionFindNode::Size(1); size]),
+            versions: vec![(0, size)],
+        }
+    }
+
+    /// 最新のバージョンを取得
+    pub fn latest_version(&self) -> usize {
+        self.versions.len() - 1
+    }
+
+    /// versionでのルートの数を取得
+    pub fn get_root_count(&self, version: usize) -> usize {
+        self.versions[version].1
+    }
+
+    /// versionでnode_aとnode_bそれぞれを含むグループを結合した新しいバージョンを作成し、そのバージョンを返却
+    /// 既に同一グループの場合も、versionと同じ状態の新しいバージョンを作る
+    pub fn merge(&mut self, version: usize, node_a: usize, node_b: usize) -> usize {
+        let (array_version, root_count) = self.versions[version];
+        let (root_a, size_a) = self.get_root_and_size(version, node_a);
+        let (root_b, size_b) = self.get_root_and_size(version, node_b);
+        let new_version = if root_a != root_b {
+            let (root_bigger, root_smaller) = if size_a >= size_b {
+                (root_a, root_b)
+            } else {
+                (root_b, root_a)
+            };
+            let tmp_version = self.array.set(
+                array_version,
+                root_smaller,
+                UnionFindNode::Parent(root_bigger),
+            );
+            let array_version = self.array.set(
+                tmp_version,
+                root_bigger,
+                UnionFindNode::Size(size_a + size_b),
+            );
+            (array_version, root_count - 1)
+        } else {
+            (array_version, root_count)
+        };
+        self.versions.push(new_version);
+        self.versions.len() - 1
+    }
+
+    /// versionでnode_aとnode_bが同一のグループか判定
+    pub fn same(&self, version: usize, node_a: usize, node_b: usize) -> bool {
+        self.get_root_and_size(version, node_a).0 == self.get_root_and_size(version, node_b).0
+    }
+
+    /// versionでnodeが属するグループのsizeを取得
+    pub fn size(&self, version: usize, node: usize) -> usize {
+        self.get_root_and_size(version, node).1
+    }
+
+    /// versionでのnodeのroot_nodeとnodeが属するグループのsizeを取得
+    pub fn get_root_and_size(&self, version: usize, node: usize) -> (usize, usize) {
+        let array_version = self.versions[version].0;
+        let mut node_tmp: usize = node;
+        loop {
+            match *self.array.get(array_version, node_tmp) {
+                UnionFindNode::Parent(parent) => node_tmp = parent,
+                UnionFindNode::Size(size) => return (node_tmp, size),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::union_find::UnionFind;
+
+    #[test]
+    fn test_versions() {
+        let mut uf = PersistentUnionFind::new(5);
+        let v1 = uf.merge(0, 0, 1);
+        let v2 = uf.merge(v1, 1, 2);
+        let v3 = uf.merge(v2, 3, 4);
+        assert_eq!(uf.latest_version(), 3);
+
+        assert!(!uf.same(0, 0, 1));
+        assert!(uf.same(v1, 0, 1));
+        assert!(!uf.same(v1, 0, 2));
+        assert!(uf.same(v2, 0, 2));
+        assert!(!uf.same(v2, 3, 4));
+        assert!(uf.same(v3, 3, 4));
+
+        assert_eq!(uf.size(0, 2), 1);
+        assert_eq!(uf.size(v2, 2), 3);
+        assert_eq!(uf.get_root_count(0), 5);
+        assert_eq!(uf.get_root_count(v3), 2);
+    }
+
+    #[test]
+    fn test_branching_versions() {
+        let mut uf = PersistentUnionFind::new(4);
+        let v1 = uf.merge(0, 0, 1);
+        // v1から2方向に分岐する
+        let v2 = uf.merge(v1, 1, 2);
+        let v3 = uf.merge(v1, 1, 3);
+        assert!(uf.same(v2, 0, 2));
+        assert!(!uf.same(v2, 0, 3));
+        assert!(uf.same(v3, 0, 3));
+        assert!(!uf.same(v3, 0, 2));
+        // 既に同一グループでも新しいバージョンができる
+        let v4 = uf.merge(v3, 3, 0);
+        assert_eq!(v4, 4);
+        assert_eq!(uf.get_root_count(v4), uf.get_root_count(v3));
+    }
+
+    #[test]
+    fn test_compare_with_union_find() {
+        let n = 30;
+        let edges: Vec<(usize, usize)> = (0..60).map(|i| ((i * 7) % n, (i * 13 + 5) % n)).collect();
+        let mut puf = PersistentUnionFind::new(n);
+        let mut version = 0;
+        let mut snapshots = vec![];
+        let mut uf = UnionFind::new(n);
+        for &(a, b) in &edges {
+            version = puf.merge(version, a, b);
+            uf.merge(a, b);
+            snapshots.push(uf.clone());
+        }
+        for (i, snapshot) in snapshots.iter_mut().enumerate() {
+            for a in 0..n {
+                for b in 0..n {
+                    assert_eq!(puf.same(i + 1, a, b), snapshot.same(a, b));
+                }
+                assert_eq!(puf.size(i + 1, a), snapshot.get_root_and_size(a).1);
+            }
+            assert_eq!(puf.get_root_count(i + 1), snapshot.get_root_count());
+        }
+    }
+}
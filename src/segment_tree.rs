@@ -43,10 +43,7 @@ where
                 }
             }
         }
-        SegmentTree {
-            cells: cells,
-            cal: cal,
-        }
+        SegmentTree { cells, cal }
     }
     pub fn update(&mut self, pos: usize, v: T) {
         self.cells[0][pos] = v;
@@ -110,15 +107,15 @@ mod tests {
 
     #[test]
     fn test_new() {
-        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], max);
         assert_eq!(
             st.cells,
             vec![vec![1, 2, 3, 4, 5, 6], vec![2, 4, 6], vec![4, 6], vec![6]]
         );
-        let st = SegmentTree::new(vec![1, 2], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2], max);
         assert_eq!(st.cells, vec![vec![1, 2], vec![2]]);
 
-        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 2], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 2], max);
         assert_eq!(
             st.cells,
             vec![
@@ -133,7 +130,7 @@ mod tests {
 
     #[test]
     fn test_update() {
-        let mut st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], |a, b| max(a, b));
+        let mut st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], max);
         st.update(0, 7);
         assert_eq!(
             st.cells,
@@ -149,13 +146,13 @@ mod tests {
             st.cells,
             vec![vec![1, 2, 3, 2, 5, 6], vec![2, 3, 6], vec![3, 6], vec![6]]
         );
-        let mut st = SegmentTree::new(vec![2, 1], |a, b| min(a, b));
+        let mut st = SegmentTree::new(vec![2, 1], min);
         st.update(0, 1);
         assert_eq!(st.cells, vec![vec![1, 1], vec![1]]);
         st.update(1, 0);
         assert_eq!(st.cells, vec![vec![1, 0], vec![0]]);
 
-        let mut st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 2], |a, b| min(a, b));
+        let mut st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 2], min);
         assert_eq!(
             st.cells,
             vec![
@@ -192,17 +189,17 @@ mod tests {
 
     #[test]
     fn test_query() {
-        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6], max);
         assert_eq!(st.query(0, 5), 6);
         assert_eq!(st.query(0, 0), 1);
         assert_eq!(st.query(0, 1), 2);
         assert_eq!(st.query(3, 5), 6);
         assert_eq!(st.query(0, 4), 5);
-        let st = SegmentTree::new(vec![1, 2], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2], max);
         assert_eq!(st.query(0, 0), 1);
         assert_eq!(st.query(1, 1), 2);
 
-        let st = SegmentTree::new(vec![1, 2, 5, 1, 9, 6, 7, 8, 2], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2, 5, 1, 9, 6, 7, 8, 2], max);
         assert_eq!(st.query(0, 0), 1);
         assert_eq!(st.query(1, 1), 2);
         assert_eq!(st.query(0, 2), 5);
@@ -212,7 +209,7 @@ mod tests {
     }
     #[test]
     fn test_cell_range() {
-        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7], |a, b| max(a, b));
+        let st = SegmentTree::new(vec![1, 2, 3, 4, 5, 6, 7], max);
         assert_eq!(
             st.cells,
             vec![
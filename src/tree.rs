@@ -0,0 +1,177 @@
+use crate::union_find::UnionFind;
+
+/// TarjanのオフラインLCA
+/// rootを根とする木について、各クエリ(u, v)の最小共通祖先を求める O((n + q) log n)
+/// UnionFindは問い合わせたnodeのみを経路圧縮するため、計算量はunion by sizeによるlog nで抑える
+/// 再帰を使わず明示的なスタックでDFSするため、深い木でもスタックオーバーフローしない
+/// rootから到達できない頂点を含むクエリの答えはNone
+///
+/// # Arguments
+/// * `tree` - 無向の隣接リスト
+/// * `root` - 根
+/// * `queries` - LCAを求めたい頂点の組
+pub fn lca_offline(
+    tree: &[Vec<usize>],
+    root: usize,
+    queries: &[(usize, usize)],
+) -> Vec<Option<usize>> {
+    let n = tree.len();
+    // 頂点 -> (クエリの相手, クエリ番号)
+    let mut queries_of: Vec<Vec<(usize, usize)>> = vec![vec![]; n];
+    for (i, &(u, v)) in queries.iter().enumerate() {
+        queries_of[u].push((v, i));
+        queries_of[v].push((u, i));
+    }
+
+    let mut uf = UnionFind::new(n);
+    // UnionFindのroot_node -> そのグループの頂点のうち、現在DFSで最も浅い頂点
+    let mut ancestor: Vec<usize> = (0..n).collect();
+    let mut visited = vec![false; n];
+    let mut finished = vec![false; n];
+    let mut answers = vec![None; queries.len()];
+    // (頂点, 次に見る隣接頂点のインデックス)
+    let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+    visited[root] = true;
+    while let Some((node, next)) = stack.last_mut() {
+        let node = *node;
+        if let Some(&child) = tree[node].get(*next) {
+            *next += 1;
+            if !visited[child] {
+                visited[child] = true;
+                stack.push((child, 0));
+            }
+            continue;
+        }
+
+        // nodeの部分木を探索し終えた
+        stack.pop();
+        finished[node] = true;
+        for &(other, i) in &queries_of[node] {
+            if finished[other] {
+                answers[i] = Some(ancestor[uf.get_root_and_size(other).0]);
+            }
+        }
+        if let Some(&(parent, _)) = stack.last() {
+            uf.merge(parent, node);
+            ancestor[uf.get_root_and_size(parent).0] = parent;
+        }
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_tree(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut tree = vec![vec![]; n];
+        for &(a, b) in edges {
+            tree[a].push(b);
+            tree[b].push(a);
+        }
+        tree
+    }
+
+    // 親を辿る愚直なLCA
+    fn naive_lca(parent: &[usize], depth: &[usize], mut u: usize, mut v: usize) -> usize {
+        while depth[u] > depth[v] {
+            u = parent[u];
+        }
+        while depth[v] > depth[u] {
+            v = parent[v];
+        }
+        while u != v {
+            u = parent[u];
+            v = parent[v];
+        }
+        u
+    }
+
+    #[test]
+    fn test_lca_offline() {
+        //        0
+        //      / | \
+        //     1  2  3
+        //    / \     \
+        //   4   5     6
+        //       |
+        //       7
+        let tree = build_tree(8, &[(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (5, 7)]);
+        let queries = vec![
+            (4, 7),
+            (7, 4),
+            (4, 5),
+            (7, 6),
+            (2, 2),
+            (1, 7),
+            (6, 3),
+            (0, 7),
+        ];
+        assert_eq!(
+            lca_offline(&tree, 0, &queries),
+            vec![
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(0),
+                Some(2),
+                Some(1),
+                Some(3),
+                Some(0)
+            ]
+        );
+        // 根を変えると答えも変わる
+        assert_eq!(
+            lca_offline(&tree, 7, &[(4, 6), (0, 2)]),
+            vec![Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let tree = build_tree(4, &[(0, 1), (2, 3)]);
+        assert_eq!(
+            lca_offline(&tree, 0, &[(0, 1), (1, 2), (2, 3)]),
+            vec![Some(0), None, None]
+        );
+    }
+
+    #[test]
+    fn test_deep_path() {
+        // 再帰DFSではスタックが溢れる深さのパス
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (0..n - 1).map(|i| (i, i + 1)).collect();
+        let tree = build_tree(n, &edges);
+        let queries = vec![(n - 1, 0), (n - 1, n - 2), (100_000, 150_000), (5, 5)];
+        assert_eq!(
+            lca_offline(&tree, 0, &queries),
+            vec![Some(0), Some(n - 2), Some(100_000), Some(5)]
+        );
+    }
+
+    #[test]
+    fn test_compare_with_naive() {
+        let n = 300;
+        // 深いパスと枝分かれが混ざった木
+        let mut parent = vec![0; n];
+        let mut depth = vec![0; n];
+        let mut edges = vec![];
+        for i in 1..n {
+            parent[i] = if i % 3 == 0 {
+                i * 2_654_435_761 % 1_000_003 % i
+            } else {
+                i - 1
+            };
+            depth[i] = depth[parent[i]] + 1;
+            edges.push((parent[i], i));
+        }
+        let tree = build_tree(n, &edges);
+        let queries: Vec<(usize, usize)> = (0..2000)
+            .map(|i| ((i * 31) % n, (i * 17 + 11) % n))
+            .collect();
+        let answers = lca_offline(&tree, 0, &queries);
+        for (&(u, v), answer) in queries.iter().zip(answers) {
+            assert_eq!(answer, Some(naive_lca(&parent, &depth, u, v)));
+        }
+    }
+}
@@ -1,5 +1,5 @@
 #[derive(Debug, Clone, Copy)]
-enum UnionFindNode {
+pub(crate) enum UnionFindNode {
     Parent(usize),
     Size(usize),
 }
@@ -9,16 +9,26 @@ pub struct UnionFind {
     // ルートノードであるとき->グループのサイズ
     // ルートノードでないとき->親
     graph: Vec<UnionFindNode>,
+    // ルートノードであるとき->グループ内でmergeされた辺の数
+    edge_count: Vec<usize>,
     root_count: usize,
 }
 
 impl UnionFind {
     pub fn new(size: usize) -> Self {
-        let new_union_find = Self {
+        Self {
             graph: vec![UnionFindNode::Size(1); size],
+            edge_count: vec![0; size],
             root_count: size,
-        };
-        new_union_find
+        }
+    }
+
+    /// 単独のグループとしてnodeを1つ追加し、そのnode番号を返却
+    pub fn add_node(&mut self) -> usize {
+        self.graph.push(UnionFindNode::Size(1));
+        self.edge_count.push(0);
+        self.root_count += 1;
+        self.graph.len() - 1
     }
 
     /// ルートの数を取得
@@ -26,8 +36,10 @@ impl UnionFind {
         self.root_count
     }
 
-    /// node_aとnode_bそれぞれを含むグループを結合する
-    pub fn merge(&mut self, node_a: usize, node_b: usize) {
+    /// node_aとnode_bそれぞれを含むグループを結合し、辺node_a-node_bを数える
+    /// Some((新しいroot_node, 吸収された側の元root_node)): 結合した時
+    /// None: 既に同一グループだった時 (辺が閉路を作った時)
+    pub fn merge(&mut self, node_a: usize, node_b: usize) -> Option<(usize, usize)> {
         let (root_a, size_a) = self.get_root_and_size(node_a);
         let (root_b, size_b) = self.get_root_and_size(node_b);
         if root_a != root_b {
@@ -39,9 +51,37 @@ impl UnionFind {
             };
             self.graph[root_smaller] = UnionFindNode::Parent(root_bigger);
             self.graph[root_bigger] = UnionFindNode::Size(size_a + size_b);
+            self.edge_count[root_bigger] += self.edge_count[root_smaller] + 1;
+            Some((root_bigger, root_smaller))
+        } else {
+            self.edge_count[root_a] += 1;
+            None
         }
     }
 
+    /// nodeが属するグループ内でmergeされた辺の数を取得 (多重辺・自己ループを含む)
+    pub fn edge_count(&mut self, node: usize) -> usize {
+        let root = self.get_root_and_size(node).0;
+        self.edge_count[root]
+    }
+
+    /// nodeが属するグループが木 (辺の数 = 頂点数 - 1) か判定
+    pub fn is_tree(&mut self, node: usize) -> bool {
+        let (root, size) = self.get_root_and_size(node);
+        self.edge_count[root] + 1 == size
+    }
+
+    /// nodeが属するグループがなもりグラフ (辺の数 = 頂点数) か判定
+    pub fn is_unicyclic(&mut self, node: usize) -> bool {
+        let (root, size) = self.get_root_and_size(node);
+        self.edge_count[root] == size
+    }
+
+    /// nodeが属するグループが閉路を含むか判定
+    pub fn has_cycle(&mut self, node: usize) -> bool {
+        !self.is_tree(node)
+    }
+
     /// node_aとnode_bが同一のグループか判定
     pub fn same(&mut self, node_a: usize, node_b: usize) -> bool {
         self.get_root_and_size(node_a).0 == self.get_root_and_size(node_b).0
@@ -63,6 +103,51 @@ impl UnionFind {
             }
         }
     }
+
+    /// グループごとのnodeのリストを取得
+    /// グループは最小のnode番号順、グループ内は昇順
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_index = vec![usize::MAX; self.graph.len()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for node in 0..self.graph.len() {
+            let root = self.get_root_and_size(node).0;
+            if group_index[root] == usize::MAX {
+                group_index[root] = groups.len();
+                groups.push(vec![]);
+            }
+            groups[group_index[root]].push(node);
+        }
+        groups
+    }
+
+    /// nodeのroot_nodeを取得
+    /// 経路圧縮を行わないため&selfで呼び出せる
+    pub fn find_immut(&self, node: usize) -> usize {
+        self.get_root_and_size_immut(node).0
+    }
+
+    /// node_aとnode_bが同一のグループか判定
+    /// 経路圧縮を行わないため&selfで呼び出せる
+    pub fn same_immut(&self, node_a: usize, node_b: usize) -> bool {
+        self.find_immut(node_a) == self.find_immut(node_b)
+    }
+
+    /// nodeが属するグループのsizeを取得
+    /// 経路圧縮を行わないため&selfで呼び出せる
+    pub fn size_immut(&self, node: usize) -> usize {
+        self.get_root_and_size_immut(node).1
+    }
+
+    // 経路圧縮なしでroot_nodeとsizeを取得
+    fn get_root_and_size_immut(&self, node: usize) -> (usize, usize) {
+        let mut node_tmp: usize = node;
+        loop {
+            match self.graph[node_tmp] {
+                UnionFindNode::Parent(parent) => node_tmp = parent,
+                UnionFindNode::Size(size) => return (node_tmp, size),
+            }
+        }
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -93,4 +178,94 @@ mod tests {
         assert!(matches!(uf.get_root_and_size(0), (0, 3) | (1, 3) | (2, 3)));
         assert!(matches!(uf.get_root_and_size(3), (3, 2) | (4, 2)));
     }
+
+    #[test]
+    fn test_merge_result() {
+        let mut uf = UnionFind::new(4);
+        assert_eq!(uf.merge(0, 1), Some((0, 1)));
+        // サイズの大きい側が新しいroot_nodeになる
+        assert_eq!(uf.merge(2, 0), Some((0, 2)));
+        assert_eq!(uf.merge(1, 2), None);
+        assert_eq!(uf.merge(3, 3), None);
+        assert_eq!(uf.get_root_count(), 2);
+    }
+
+    #[test]
+    fn test_edge_count() {
+        let mut uf = UnionFind::new(7);
+        // 0-1-2 の木
+        uf.merge(0, 1);
+        uf.merge(1, 2);
+        // 3-4-5-3 の閉路
+        uf.merge(3, 4);
+        uf.merge(4, 5);
+        uf.merge(5, 3);
+        assert_eq!(uf.edge_count(2), 2);
+        assert!(uf.is_tree(0));
+        assert!(!uf.is_unicyclic(0));
+        assert!(!uf.has_cycle(0));
+        assert_eq!(uf.edge_count(4), 3);
+        assert!(!uf.is_tree(4));
+        assert!(uf.is_unicyclic(4));
+        assert!(uf.has_cycle(4));
+        assert!(uf.is_tree(6));
+        assert_eq!(uf.edge_count(6), 0);
+
+        // 木となもりグラフを繋ぐとなもりグラフ
+        uf.merge(2, 3);
+        assert!(uf.is_unicyclic(0));
+        assert_eq!(uf.edge_count(5), 6);
+        // 自己ループも閉路として数える
+        uf.merge(6, 6);
+        assert!(uf.is_unicyclic(6));
+        uf.merge(0, 5);
+        assert!(!uf.is_unicyclic(0));
+        assert!(uf.has_cycle(0));
+    }
+
+    #[test]
+    fn test_add_node() {
+        let mut uf = UnionFind::new(2);
+        uf.merge(0, 1);
+        assert_eq!(uf.add_node(), 2);
+        assert_eq!(uf.get_root_count(), 2);
+        uf.merge(2, 0);
+        assert_eq!(uf.get_root_count(), 1);
+        assert_eq!(uf.get_root_and_size(2).1, 3);
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut uf = UnionFind::new(6);
+        uf.merge(4, 1);
+        uf.merge(3, 0);
+        uf.merge(1, 5);
+        assert_eq!(uf.groups(), vec![vec![0, 3], vec![1, 4, 5], vec![2]]);
+        assert!(UnionFind::new(0).groups().is_empty());
+    }
+
+    #[test]
+    fn test_immut_queries() {
+        let mut uf = UnionFind::new(6);
+        uf.merge(0, 1);
+        uf.merge(1, 2);
+        uf.merge(3, 4);
+        let uf_ref = &uf;
+        assert!(uf_ref.same_immut(0, 2));
+        assert!(!uf_ref.same_immut(0, 3));
+        assert_eq!(uf_ref.find_immut(4), uf_ref.find_immut(3));
+        assert_eq!(uf_ref.size_immut(2), 3);
+        assert_eq!(uf_ref.size_immut(4), 2);
+        assert_eq!(uf_ref.size_immut(5), 1);
+
+        // クロージャ内から共有参照で問い合わせる
+        let connected: Vec<usize> = (0..6).filter(|&i| uf_ref.same_immut(0, i)).collect();
+        assert_eq!(connected, vec![0, 1, 2]);
+
+        // 経路圧縮ありの結果と一致する
+        for i in 0..6 {
+            let root = uf.find_immut(i);
+            assert_eq!(uf.get_root_and_size(i).0, root);
+        }
+    }
 }
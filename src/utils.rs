@@ -1,6 +1,9 @@
+use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Display;
 use std::hash::Hash;
+use std::io::{self, Read, Stdout, Write};
+use std::iter::FromIterator;
 use std::mem::swap;
 
 /// 複数の引数のうち最小値を返す
@@ -63,24 +66,309 @@ macro_rules! debug_print {
     };
 }
 
-/// ベクタの各値をデリミタで区切って標準出力する
-pub fn print_vector<T: Display>(vector: &Vec<T>, delimiter: &str) {
-    let len = vector.len();
-    for i in 0..len - 1 {
-        print!("{}{}", vector[i], delimiter);
+/// input!の内部実装 1つの値を読み込む
+/// 型は`usize`などReadableを実装した型、`(T, U)`のタプル、`[T; n]`のベクタを組み合わせて指定する
+#[doc(hidden)]
+#[macro_export]
+macro_rules! read_value {
+    ($sc:expr, ( $($t:tt),* )) => {
+        ( $($crate::read_value!($sc, $t)),* )
+    };
+    ($sc:expr, [ $t:tt ; $len:expr ]) => {
+        (0..$len).map(|_| $crate::read_value!($sc, $t)).collect::<Vec<_>>()
+    };
+    ($sc:expr, $t:ty) => {
+        $sc.read::<$t>()
+    };
+}
+
+/// input!の内部実装 `変数名: 型` の並びを先頭から1つずつ読み込む
+#[doc(hidden)]
+#[macro_export]
+macro_rules! input_inner {
+    (@source [$($src:tt)*]) => {};
+    (@source [$($src:tt)*] , $($rest:tt)*) => {
+        $crate::input_inner!(@source [$($src)*] $($rest)*);
+    };
+    (@source [$($src:tt)*] mut $var:ident : $t:tt $($rest:tt)*) => {
+        let mut $var = $crate::input_inner!(@read [$($src)*] $t);
+        $crate::input_inner!(@source [$($src)*] $($rest)*);
+    };
+    (@source [$($src:tt)*] $var:ident : $t:tt $($rest:tt)*) => {
+        let $var = $crate::input_inner!(@read [$($src)*] $t);
+        $crate::input_inner!(@source [$($src)*] $($rest)*);
+    };
+    (@read [stdin] $t:tt) => {
+        $crate::with_stdin_scanner(|sc| $crate::read_value!(sc, $t))
+    };
+    (@read [from $sc:expr] $t:tt) => {
+        $crate::read_value!($sc, $t)
+    };
+}
+
+/// 入力を読み込み、変数を宣言する
+/// `from scanner,` を先頭に付けるとそのScannerから、付けない場合は標準入力から読み込む
+///
+/// 型は`usize`などReadableを実装した型、`(T, U)`のタプル、`[T; n]`のベクタを組み合わせて指定する
+/// 型の各部分は1トークンとして読むため、`procon_library::Chars`のようなパスは書けない
+/// `Chars`、`Bytes`、`Usize1`はuseで名前を取り込んでから使うこと
+///
+/// # Examples
+/// ```
+/// use procon_library::{input, Chars, Scanner, Usize1};
+///
+/// let mut sc = Scanner::new("3 2\n1 2 3\n1 3\n#.\n");
+/// input! {
+///     from sc,
+///     n: usize,
+///     mut k: usize,
+///     a: [i64; n],
+///     edge: (Usize1, Usize1),
+///     grid: [Chars; 1],
+/// }
+/// k -= 1;
+/// assert_eq!((n, k), (3, 1));
+/// assert_eq!(a, vec![1, 2, 3]);
+/// assert_eq!(edge, (0, 2));
+/// assert_eq!(grid, vec![vec!['#', '.']]);
+/// ```
+#[macro_export]
+macro_rules! input {
+    (from $sc:expr, $($rest:tt)*) => {
+        $crate::input_inner!(@source [from $sc] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::input_inner!(@source [stdin] $($rest)*);
+    };
+}
+
+/// 空白区切りのトークンを先頭から順に読み込むスキャナ
+/// 入力全体をバッファに持ち、読み込み位置を進めていく
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl Scanner {
+    /// 文字列を入力として作成
+    pub fn new(source: &str) -> Self {
+        Self {
+            buffer: source.as_bytes().to_vec(),
+            position: 0,
+        }
+    }
+
+    /// 標準入力を最後まで読み込んで作成
+    pub fn from_stdin() -> Self {
+        let mut buffer = vec![];
+        std::io::stdin().read_to_end(&mut buffer).unwrap();
+        Self {
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// 次のトークンを取得 入力の終わりに達した場合はNone
+    pub fn next_token(&mut self) -> Option<&str> {
+        while self
+            .buffer
+            .get(self.position)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        let start = self.position;
+        while self
+            .buffer
+            .get(self.position)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        if start == self.position {
+            None
+        } else {
+            Some(std::str::from_utf8(&self.buffer[start..self.position]).unwrap())
+        }
+    }
+
+    /// 型Rとして次の値を読み込む
+    pub fn read<R: Readable>(&mut self) -> R::Output {
+        R::read(self)
     }
-    println!("{}", vector[len - 1]);
 }
 
-/// 二次元ベクタの各値をデリミタで区切って標準出力する
-pub fn print_2d_vector<T: Display>(grid: &Vec<Vec<T>>, delimiter: &str) {
-    for vector in grid {
-        let v_len: usize = vector.len();
-        for i in 0..v_len - 1 {
-            print!("{}{}", vector[i], delimiter);
+/// Scannerから読み込める型
+pub trait Readable {
+    type Output;
+    fn read(scanner: &mut Scanner) -> Self::Output;
+}
+
+macro_rules! impl_readable_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl Readable for $t {
+                type Output = $t;
+                fn read(scanner: &mut Scanner) -> $t {
+                    let token = scanner.next_token().expect("入力が足りません");
+                    token.parse().unwrap_or_else(|_| {
+                        panic!("{}を{}として読み込めません", token, stringify!($t))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_readable_from_str!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, String
+);
+
+/// 1-indexedの値を0-indexedのusizeとして読み込む
+#[derive(Debug)]
+pub enum Usize1 {}
+
+impl Readable for Usize1 {
+    type Output = usize;
+    fn read(scanner: &mut Scanner) -> usize {
+        usize::read(scanner)
+            .checked_sub(1)
+            .expect("Usize1に0は読み込めません")
+    }
+}
+
+/// トークンを文字のベクタとして読み込む
+#[derive(Debug)]
+pub enum Chars {}
+
+impl Readable for Chars {
+    type Output = Vec<char>;
+    fn read(scanner: &mut Scanner) -> Vec<char> {
+        String::read(scanner).chars().collect()
+    }
+}
+
+/// トークンをバイト列として読み込む
+#[derive(Debug)]
+pub enum Bytes {}
+
+impl Readable for Bytes {
+    type Output = Vec<u8>;
+    fn read(scanner: &mut Scanner) -> Vec<u8> {
+        String::read(scanner).into_bytes()
+    }
+}
+
+thread_local! {
+    static STDIN_SCANNER: RefCell<Option<Scanner>> = const { RefCell::new(None) };
+}
+
+/// 標準入力のScannerを使って処理する
+/// 初回の呼び出し時に標準入力を最後まで読み込み、以降は同じScannerを使い回す
+pub fn with_stdin_scanner<R, F>(f: F) -> R
+where
+    F: FnOnce(&mut Scanner) -> R,
+{
+    STDIN_SCANNER.with(|cell| {
+        let mut scanner = cell.borrow_mut();
+        f(scanner.get_or_insert_with(Scanner::from_stdin))
+    })
+}
+
+/// 出力をバッファに溜めてまとめて書き込むライタ
+/// 既定ではdrop時にflushする
+#[derive(Debug)]
+pub struct Output<W: Write> {
+    writer: W,
+    buffer: Vec<u8>,
+    flush_on_drop: bool,
+}
+
+impl Output<Stdout> {
+    /// 標準出力に書き込むOutputを作成
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: vec![],
+            flush_on_drop: true,
         }
-        println!("{}", vector[v_len - 1]);
     }
+
+    /// drop時にflushするかを設定
+    pub fn flush_on_drop(&mut self, enabled: bool) {
+        self.flush_on_drop = enabled;
+    }
+
+    /// 各値をデリミタで区切って書き込む 改行はしない
+    pub fn join<T, I>(&mut self, values: I, delimiter: &str)
+    where
+        T: Display,
+        I: IntoIterator<Item = T>,
+    {
+        for (i, v) in values.into_iter().enumerate() {
+            if i > 0 {
+                self.buffer.extend_from_slice(delimiter.as_bytes());
+            }
+            write!(self.buffer, "{}", v).unwrap();
+        }
+    }
+
+    /// ベクタの各値をデリミタで区切って1行で書き込む
+    pub fn write_vec<T: Display>(&mut self, vector: &[T], delimiter: &str) {
+        self.join(vector, delimiter);
+        self.buffer.push(b'\n');
+    }
+
+    /// 二次元ベクタの各行をデリミタで区切って書き込む
+    pub fn write_grid<T: Display>(&mut self, grid: &[Vec<T>], delimiter: &str) {
+        for vector in grid {
+            self.write_vec(vector, delimiter);
+        }
+    }
+
+    /// trueなら"Yes"、falseなら"No"を1行で書き込む
+    pub fn yes_no(&mut self, condition: bool) {
+        self.buffer
+            .extend_from_slice(if condition { b"Yes\n" } else { b"No\n" });
+    }
+}
+
+impl<W: Write> Write for Output<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for Output<W> {
+    fn drop(&mut self) {
+        if self.flush_on_drop {
+            let _ = self.flush();
+        }
+    }
+}
+
+/// ベクタの各値をデリミタで区切って標準出力する
+pub fn print_vector<T: Display>(vector: &[T], delimiter: &str) {
+    Output::stdout().write_vec(vector, delimiter);
+}
+
+/// 二次元ベクタの各値をデリミタで区切って標準出力する
+pub fn print_2d_vector<T: Display>(grid: &[Vec<T>], delimiter: &str) {
+    Output::stdout().write_grid(grid, delimiter);
 }
 
 /// HashMapの、keyのキーの値とcandidate_vをcmpメソッドに基づいて比較
@@ -101,7 +389,7 @@ pub fn cmp_and_replace_value_in_hashmap<T, U, F>(
     F: FnMut(&U, &U) -> bool,
 {
     if let Some(existing_v) = map.get(key) {
-        if cmp(&candidate_v, &existing_v) {
+        if cmp(&candidate_v, existing_v) {
             map.insert(*key, candidate_v);
         }
     } else {
@@ -110,29 +398,883 @@ pub fn cmp_and_replace_value_in_hashmap<T, U, F>(
     }
 }
 
+/// 二分探索の判定結果の並び方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotone {
+    /// 小さい側がok、大きい側がng okとなる最大の値を探す
+    OkThenNg,
+    /// 小さい側がng、大きい側がok okとなる最小の値を探す
+    NgThenOk,
+}
+
+/// 二分探索で扱える整数型
+pub trait PrimitiveInteger: Copy + Ord {
+    /// self + 1
+    fn successor(self) -> Self;
+    /// self - 1
+    fn predecessor(self) -> Self;
+    /// selfとotherの中点 (切り捨て) をオーバーフローせずに求める
+    fn midpoint_floor(self, other: Self) -> Self;
+    /// selfとotherの中点 (切り上げ) をオーバーフローせずに求める
+    fn midpoint_ceil(self, other: Self) -> Self;
+}
+
+macro_rules! impl_primitive_integer {
+    ($($t:ty),*) => {
+        $(
+            impl PrimitiveInteger for $t {
+                fn successor(self) -> Self {
+                    self + 1
+                }
+                fn predecessor(self) -> Self {
+                    self - 1
+                }
+                fn midpoint_floor(self, other: Self) -> Self {
+                    (self & other) + ((self ^ other) >> 1)
+                }
+                fn midpoint_ceil(self, other: Self) -> Self {
+                    (self | other) - ((self ^ other) >> 1)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// 閉区間[lo, hi]で二分探索する
+/// OkThenNgならokとなる最大の値、NgThenOkならokとなる最小の値を返す okとなる値がない場合はNone
+/// 型の最小値・最大値を端にしてもオーバーフローしない
+///
+/// # Arguments
+/// * `lo` - 探索範囲の下端
+/// * `hi` - 探索範囲の上端
+/// * `monotone` - judgeの結果の並び方
+/// * `judge` - midを元に判定するクロージャ okの場合true
+pub fn binary_search_by<T, F>(lo: T, hi: T, monotone: Monotone, mut judge: F) -> Option<T>
+where
+    T: PrimitiveInteger,
+    F: FnMut(T) -> bool,
+{
+    if lo > hi {
+        return None;
+    }
+    match monotone {
+        Monotone::OkThenNg => {
+            if !judge(lo) {
+                return None;
+            }
+            let (mut ok, mut ng_side) = (lo, hi);
+            while ok < ng_side {
+                let mid = ok.midpoint_ceil(ng_side);
+                if judge(mid) {
+                    ok = mid;
+                } else {
+                    ng_side = mid.predecessor();
+                }
+            }
+            Some(ok)
+        }
+        Monotone::NgThenOk => {
+            if !judge(hi) {
+                return None;
+            }
+            let (mut ng_side, mut ok) = (lo, hi);
+            while ng_side < ok {
+                let mid = ng_side.midpoint_floor(ok);
+                if judge(mid) {
+                    ok = mid;
+                } else {
+                    ng_side = mid.successor();
+                }
+            }
+            Some(ok)
+        }
+    }
+}
+
 /// 二分探索関数
+/// okとなる最大の値を返す okとなる値がない場合はedge_l - 1
 ///
 /// # Arguments
 /// * `edge_l` - 橋インデックス片方
 /// * `edge_r` - 橋インデックスもう片方
 /// * `judge` - midを元に判定するクロージャ okの場合true
-pub fn bin_sch<F>(edge_l: usize, edge_r: usize, mut judge: F) -> isize
+pub fn bin_sch<F>(edge_l: usize, edge_r: usize, judge: F) -> isize
 where
     F: FnMut(usize) -> bool,
 {
-    let mut ok: isize = edge_l as isize - 1isize;
-    let mut ng: isize = (edge_r + 1) as isize;
-    while ng - ok > 1 {
-        let mid: isize = ((ok + ng) / 2) as isize;
-        if judge(mid as usize) {
+    binary_search_by(edge_l, edge_r, Monotone::OkThenNg, judge)
+        .map_or(edge_l as isize - 1, |ok| ok as isize)
+}
+
+/// 実数の探索の終了条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Termination {
+    /// 指定回数だけ区間を縮める
+    Iterations(usize),
+    /// 区間の幅が指定値以下になるまで縮める
+    Epsilon(f64),
+}
+
+impl Termination {
+    // step回縮めた幅widthの区間でまだ探索を続けるか判定
+    fn should_continue(&self, step: usize, width: f64) -> bool {
+        match *self {
+            Termination::Iterations(count) => step < count,
+            Termination::Epsilon(eps) => width > eps,
+        }
+    }
+}
+
+/// 最小値と最大値のどちらを探すか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    Minimize,
+    Maximize,
+}
+
+impl Extremum {
+    // aがbより良いか判定
+    fn is_better<T: PartialOrd>(&self, a: &T, b: &T) -> bool {
+        match self {
+            Extremum::Minimize => a < b,
+            Extremum::Maximize => a > b,
+        }
+    }
+}
+
+/// 実数の二分探索関数
+/// okとngの間を縮めていき、最終的なok側の値を返す (ok > ngでもよい)
+/// judgeの値はok側では常にtrueなので、三分探索と違い(引数, 値)の組ではなく境界のみを返す
+///
+/// # Arguments
+/// * `ok` - judgeがtrueとなる端
+/// * `ng` - judgeがfalseとなる端
+/// * `termination` - 終了条件
+/// * `judge` - midを元に判定するクロージャ okの場合true
+pub fn bisect<F>(mut ok: f64, mut ng: f64, termination: Termination, mut judge: F) -> f64
+where
+    F: FnMut(f64) -> bool,
+{
+    let mut step = 0;
+    while termination.should_continue(step, (ok - ng).abs()) {
+        let mid = (ok + ng) / 2.0;
+        // 浮動小数点の精度の限界に達した
+        if mid == ok || mid == ng {
+            break;
+        }
+        if judge(mid) {
             ok = mid;
         } else {
             ng = mid;
         }
+        step += 1;
     }
     ok
 }
 
+/// 整数の三分探索関数
+/// 閉区間[lo, hi]で単峰なfの最小値(最大値)を探し、(引数, 値)を返す
+/// 最適値以外で同じ値が続く区間があると正しく求まらない
+///
+/// # Arguments
+/// * `lo` - 探索範囲の下端
+/// * `hi` - 探索範囲の上端
+/// * `extremum` - 最小値と最大値のどちらを探すか
+/// * `f` - 評価関数
+pub fn ternary_search_int<T, V, F>(lo: T, hi: T, extremum: Extremum, mut f: F) -> (T, V)
+where
+    T: PrimitiveInteger,
+    V: PartialOrd,
+    F: FnMut(T) -> V,
+{
+    assert!(lo <= hi);
+    if lo == hi {
+        return (lo, f(lo));
+    }
+    // f(x + 1)がf(x)より良くならない最小のxが最適値
+    let arg = binary_search_by(lo, hi.predecessor(), Monotone::NgThenOk, |x| {
+        !extremum.is_better(&f(x.successor()), &f(x))
+    })
+    .unwrap_or(hi);
+    let value = f(arg);
+    (arg, value)
+}
+
+/// 実数の三分探索関数
+/// 閉区間[lo, hi]で単峰なfの最小値(最大値)を探し、(引数, 値)を返す
+///
+/// # Arguments
+/// * `lo` - 探索範囲の下端
+/// * `hi` - 探索範囲の上端
+/// * `termination` - 終了条件
+/// * `extremum` - 最小値と最大値のどちらを探すか
+/// * `f` - 評価関数
+pub fn ternary_search_f64<F>(
+    mut lo: f64,
+    mut hi: f64,
+    termination: Termination,
+    extremum: Extremum,
+    mut f: F,
+) -> (f64, f64)
+where
+    F: FnMut(f64) -> f64,
+{
+    let mut step = 0;
+    while termination.should_continue(step, hi - lo) {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if m1 <= lo || m2 >= hi {
+            break;
+        }
+        if extremum.is_better(&f(m1), &f(m2)) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+        step += 1;
+    }
+    let arg = (lo + hi) / 2.0;
+    (arg, f(arg))
+}
+
+/// 黄金分割探索関数
+/// 閉区間[lo, hi]で単峰なfの最小値(最大値)を探し、(引数, 値)を返す
+/// 三分探索と違い、1回の縮小でfを1回しか評価しないため評価が重い時に使う
+///
+/// # Arguments
+/// * `lo` - 探索範囲の下端
+/// * `hi` - 探索範囲の上端
+/// * `termination` - 終了条件
+/// * `extremum` - 最小値と最大値のどちらを探すか
+/// * `f` - 評価関数
+pub fn golden_section_search<F>(
+    mut lo: f64,
+    mut hi: f64,
+    termination: Termination,
+    extremum: Extremum,
+    mut f: F,
+) -> (f64, f64)
+where
+    F: FnMut(f64) -> f64,
+{
+    // 1 / 黄金比
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut m1 = hi - (hi - lo) * ratio;
+    let mut m2 = lo + (hi - lo) * ratio;
+    let (mut f1, mut f2) = (f(m1), f(m2));
+    let mut step = 0;
+    while termination.should_continue(step, hi - lo) {
+        if m1 <= lo || m2 >= hi || m1 >= m2 {
+            break;
+        }
+        if extremum.is_better(&f1, &f2) {
+            // 新しい区間の右側の内分点は元のm1
+            hi = m2;
+            m2 = m1;
+            f2 = f1;
+            m1 = hi - (hi - lo) * ratio;
+            f1 = f(m1);
+        } else {
+            lo = m1;
+            m1 = m2;
+            f1 = f2;
+            m2 = lo + (hi - lo) * ratio;
+            f2 = f(m2);
+        }
+        step += 1;
+    }
+    if extremum.is_better(&f1, &f2) {
+        (m1, f1)
+    } else {
+        (m2, f2)
+    }
+}
+
+/// 座標圧縮
+/// 値を昇順・重複なしで持ち、値と圧縮後のインデックスを相互に変換する
+#[derive(Debug, Clone)]
+pub struct Compressor<T: Ord> {
+    values: Vec<T>,
+}
+
+impl<T: Ord> FromIterator<T> for Compressor<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut values: Vec<T> = iter.into_iter().collect();
+        values.sort();
+        values.dedup();
+        Self { values }
+    }
+}
+
+impl<T: Ord> Compressor<T> {
+    /// 値の種類数を取得
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// 値が1つもないか判定
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// 値の圧縮後のインデックスを取得 含まれない値の場合はNone
+    pub fn index_of(&self, value: &T) -> Option<usize> {
+        self.values.binary_search(value).ok()
+    }
+
+    /// 圧縮後のインデックスiに対応する値を取得
+    pub fn value_at(&self, i: usize) -> &T {
+        &self.values[i]
+    }
+
+    /// value以上の値のうち最小のもののインデックスを取得 (なければlen())
+    pub fn lower_bound(&self, value: &T) -> usize {
+        self.values.partition_point(|v| v < value)
+    }
+
+    /// valueより大きい値のうち最小のもののインデックスを取得 (なければlen())
+    pub fn upper_bound(&self, value: &T) -> usize {
+        self.values.partition_point(|v| v <= value)
+    }
+
+    /// 昇順・重複なしの値のスライスを取得
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+/// vを座標圧縮し、(各要素の圧縮後のインデックス, 昇順・重複なしの値) を返す
+pub fn compress<T: Ord + Clone>(v: &[T]) -> (Vec<usize>, Vec<T>) {
+    let compressor: Compressor<T> = v.iter().cloned().collect();
+    let indices = v.iter().map(|x| compressor.index_of(x).unwrap()).collect();
+    (indices, compressor.values)
+}
+
+/// 基数ソートのキーにできる整数型
+pub trait RadixKey: Copy {
+    /// キーのバイト数 (基数ソートのパス数)
+    const BYTES: usize;
+    /// 大小関係を保ったまま符号なし整数に変換する
+    fn to_radix(self) -> u64;
+}
+
+impl RadixKey for u32 {
+    const BYTES: usize = 4;
+    fn to_radix(self) -> u64 {
+        self as u64
+    }
+}
+
+impl RadixKey for u64 {
+    const BYTES: usize = 8;
+    fn to_radix(self) -> u64 {
+        self
+    }
+}
+
+impl RadixKey for usize {
+    const BYTES: usize = std::mem::size_of::<usize>();
+    fn to_radix(self) -> u64 {
+        self as u64
+    }
+}
+
+impl RadixKey for i64 {
+    const BYTES: usize = 8;
+    fn to_radix(self) -> u64 {
+        // 符号ビットを反転すると負の数が正の数より前に並ぶ
+        (self as u64) ^ (1 << 63)
+    }
+}
+
+/// 整数のスライスを基数ソートする (昇順)
+pub fn radix_sort<T: RadixKey>(v: &mut [T]) {
+    radix_sort_by_key(v, |&x| x);
+}
+
+/// keyで取り出した整数をキーにして、スライスを安定な基数ソート(LSD, 8bitずつ)する
+/// キーはソート前に1回ずつだけ取り出す O((n + 256) * K::BYTES)
+///
+/// # Arguments
+/// * `v` - ソートを行いたい任意の型のスライス
+/// * `key` - 要素からキーを取り出すクロージャ
+pub fn radix_sort_by_key<T: Clone, K: RadixKey, F>(v: &mut [T], mut key: F)
+where
+    F: FnMut(&T) -> K,
+{
+    let len = v.len();
+    // (キー, 元のインデックス)
+    let mut order: Vec<(u64, usize)> = v
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (key(x).to_radix(), i))
+        .collect();
+    let mut buffer = order.clone();
+    for pass in 0..K::BYTES {
+        let shift = pass * 8;
+        let mut counts = [0usize; 257];
+        for &(k, _) in &order {
+            counts[((k >> shift) & 0xff) as usize + 1] += 1;
+        }
+        // 全要素がこの桁で等しければ並びは変わらない
+        if counts.contains(&len) {
+            continue;
+        }
+        for digit in 0..256 {
+            counts[digit + 1] += counts[digit];
+        }
+        for &(k, i) in &order {
+            let digit = ((k >> shift) & 0xff) as usize;
+            buffer[counts[digit]] = (k, i);
+            counts[digit] += 1;
+        }
+        swap(&mut order, &mut buffer);
+    }
+    reorder(v, order.into_iter().map(|(_, i)| i));
+}
+
+/// keyで取り出した0..key_rangeの値をキーにして、スライスを安定な計数ソートする
+/// O(n + key_range)
+///
+/// # Arguments
+/// * `v` - ソートを行いたい任意の型のスライス
+/// * `key_range` - キーの上限 (キーはkey_range未満)
+/// * `key` - 要素からキーを取り出すクロージャ
+pub fn counting_sort_by_key<T: Clone, F>(v: &mut [T], key_range: usize, mut key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = v.iter().map(&mut key).collect();
+    let mut starts = vec![0; key_range + 1];
+    for &k in &keys {
+        assert!(k < key_range, "キー{}が範囲外です", k);
+        starts[k + 1] += 1;
+    }
+    for k in 0..key_range {
+        starts[k + 1] += starts[k];
+    }
+    let mut order = vec![0; v.len()];
+    for (i, &k) in keys.iter().enumerate() {
+        order[starts[k]] = i;
+        starts[k] += 1;
+    }
+    reorder(v, order.into_iter());
+}
+
+// ソート後に元のインデックスorderの順に並ぶよう、vを並び替える
+fn reorder<T: Clone, I>(v: &mut [T], order: I)
+where
+    I: Iterator<Item = usize>,
+{
+    let original = v.to_vec();
+    for (slot, i) in v.iter_mut().zip(order) {
+        *slot = original[i].clone();
+    }
+}
+
+/// vを辞書順で次の順列に並び替える
+/// vが辞書順で最後の順列だった場合は最初の順列 (昇順) に戻してfalseを返す
+pub fn next_permutation<T: Ord>(v: &mut [T]) -> bool {
+    // v[i] < v[i + 1]となる最後のi
+    let i = match (1..v.len()).rev().find(|&i| v[i - 1] < v[i]) {
+        Some(i) => i - 1,
+        None => {
+            v.reverse();
+            return false;
+        }
+    };
+    let j = (i + 1..v.len()).rev().find(|&j| v[i] < v[j]).unwrap();
+    v.swap(i, j);
+    v[i + 1..].reverse();
+    true
+}
+
+/// vを辞書順で前の順列に並び替える
+/// vが辞書順で最初の順列だった場合は最後の順列 (降順) に戻してfalseを返す
+pub fn prev_permutation<T: Ord>(v: &mut [T]) -> bool {
+    // v[i] > v[i + 1]となる最後のi
+    let i = match (1..v.len()).rev().find(|&i| v[i - 1] > v[i]) {
+        Some(i) => i - 1,
+        None => {
+            v.reverse();
+            return false;
+        }
+    };
+    let j = (i + 1..v.len()).rev().find(|&j| v[i] > v[j]).unwrap();
+    v.swap(i, j);
+    v[i + 1..].reverse();
+    true
+}
+
+/// itemsの全ての並べ方を列挙するイテレータ
+/// 位置の辞書順で列挙するため、等しい要素があると同じ並びが重複して現れる
+#[derive(Debug, Clone)]
+pub struct Permutations<T: Clone> {
+    items: Vec<T>,
+    // 次に返す並びのインデックス 列挙し終えたらNone
+    indices: Option<Vec<usize>>,
+}
+
+/// itemsの全ての並べ方 (n!通り) を列挙する
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        indices: Some((0..items.len()).collect()),
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = &self.items;
+        let indices = self.indices.as_mut()?;
+        let item = indices.iter().map(|&i| items[i].clone()).collect();
+        if !next_permutation(indices) {
+            self.indices = None;
+        }
+        Some(item)
+    }
+}
+
+/// itemsからk個を選ぶ組み合わせを列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct Combinations<T: Clone> {
+    items: Vec<T>,
+    // 次に返す組み合わせのインデックス(狭義単調増加) 列挙し終えたらNone
+    indices: Option<Vec<usize>>,
+}
+
+/// itemsからk個を選ぶ組み合わせ (nCk通り) を、位置の辞書順で列挙する
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<T> {
+    Combinations {
+        items: items.to_vec(),
+        indices: if k <= items.len() {
+            Some((0..k).collect())
+        } else {
+            None
+        },
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = &self.items;
+        let indices = self.indices.as_mut()?;
+        let item = indices.iter().map(|&i| items[i].clone()).collect();
+        let (n, k) = (items.len(), indices.len());
+        // まだ右に動かせる最後の位置を1つ進め、それより後ろを詰める
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(item)
+    }
+}
+
+/// itemsから重複を許してk個を選ぶ組み合わせを列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct CombinationsWithReplacement<T: Clone> {
+    items: Vec<T>,
+    // 次に返す組み合わせのインデックス(広義単調増加) 列挙し終えたらNone
+    indices: Option<Vec<usize>>,
+}
+
+/// itemsから重複を許してk個を選ぶ組み合わせ (nHk通り) を、位置の辞書順で列挙する
+pub fn combinations_with_replacement<T: Clone>(
+    items: &[T],
+    k: usize,
+) -> CombinationsWithReplacement<T> {
+    CombinationsWithReplacement {
+        items: items.to_vec(),
+        indices: if k == 0 || !items.is_empty() {
+            Some(vec![0; k])
+        } else {
+            None
+        },
+    }
+}
+
+impl<T: Clone> Iterator for CombinationsWithReplacement<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = &self.items;
+        let indices = self.indices.as_mut()?;
+        let item = indices.iter().map(|&i| items[i].clone()).collect();
+        let n = items.len();
+        // 最後の要素でない最後の位置を1つ進め、それより後ろを同じ値にする
+        match (0..indices.len()).rev().find(|&i| indices[i] + 1 < n) {
+            Some(i) => {
+                let next = indices[i] + 1;
+                for index in indices[i..].iter_mut() {
+                    *index = next;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(item)
+    }
+}
+
+/// 各リストから1つずつ選ぶ全ての組を列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct CartesianProduct<T: Clone> {
+    lists: Vec<Vec<T>>,
+    // 次に返す組の各リストでのインデックス 列挙し終えたらNone
+    indices: Option<Vec<usize>>,
+}
+
+/// 各リストから1つずつ選ぶ全ての組を、後ろのリストほど速く変わる順に列挙する
+pub fn cartesian_product<T: Clone>(lists: &[Vec<T>]) -> CartesianProduct<T> {
+    CartesianProduct {
+        lists: lists.to_vec(),
+        indices: if lists.iter().all(|list| !list.is_empty()) {
+            Some(vec![0; lists.len()])
+        } else {
+            None
+        },
+    }
+}
+
+impl<T: Clone> Iterator for CartesianProduct<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let lists = &self.lists;
+        let indices = self.indices.as_mut()?;
+        let item = indices
+            .iter()
+            .zip(lists)
+            .map(|(&i, list)| list[i].clone())
+            .collect();
+        // 繰り上がりのある多進数のインクリメント
+        match (0..indices.len())
+            .rev()
+            .find(|&i| indices[i] + 1 < lists[i].len())
+        {
+            Some(i) => {
+                indices[i] += 1;
+                for index in indices[i + 1..].iter_mut() {
+                    *index = 0;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(item)
+    }
+}
+
+/// nビットの全てのマスク 0..2^n を昇順に列挙する (bit全探索)
+/// nはusizeのビット数未満であること
+pub fn all_masks(n: usize) -> std::ops::Range<usize> {
+    0..mask_limit(n)
+}
+
+// 2^n nがusizeのビット数以上だと表せないのでpanicする
+fn mask_limit(n: usize) -> usize {
+    assert!(
+        n < usize::BITS as usize,
+        "ビット数{}はusizeのビット数未満である必要があります",
+        n
+    );
+    1 << n
+}
+
+/// マスクの部分集合を降順に列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct Submasks {
+    mask: usize,
+    // 次に返す部分集合 列挙し終えたらNone
+    next: Option<usize>,
+}
+
+/// maskの部分集合をmask自身から0まで降順に列挙する
+pub fn submasks(mask: usize) -> Submasks {
+    Submasks {
+        mask,
+        next: Some(mask),
+    }
+}
+
+impl Iterator for Submasks {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let sub = self.next?;
+        self.next = if sub == 0 {
+            None
+        } else {
+            Some((sub - 1) & self.mask)
+        };
+        Some(sub)
+    }
+}
+
+/// nビットの範囲でマスクを含む集合を昇順に列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct Supersets {
+    mask: usize,
+    limit: usize,
+    // 次に返す集合
+    next: usize,
+}
+
+/// nビットの範囲でmaskを含む集合をmask自身から昇順に列挙する
+/// nはusizeのビット数未満であること
+pub fn supersets(mask: usize, n: usize) -> Supersets {
+    Supersets {
+        mask,
+        limit: mask_limit(n),
+        next: mask,
+    }
+}
+
+impl Iterator for Supersets {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next >= self.limit {
+            return None;
+        }
+        let sup = self.next;
+        self.next = (sup + 1) | self.mask;
+        Some(sup)
+    }
+}
+
+/// nビットのうちちょうどkビットが立ったマスクを昇順に列挙するイテレータ
+#[derive(Debug, Clone)]
+pub struct MasksWithKBits {
+    limit: usize,
+    // 次に返すマスク 列挙し終えたらNone
+    next: Option<usize>,
+}
+
+/// nビットのうちちょうどkビットが立ったマスクを昇順に列挙する (Gosper's hack)
+/// nはusizeのビット数未満であること
+pub fn masks_with_k_bits(n: usize, k: usize) -> MasksWithKBits {
+    MasksWithKBits {
+        limit: mask_limit(n),
+        next: if k <= n { Some((1 << k) - 1) } else { None },
+    }
+}
+
+impl Iterator for MasksWithKBits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let mask = self.next?;
+        self.next = if mask == 0 {
+            // k = 0の時は0のみ
+            None
+        } else {
+            // 最下位の連続する1のブロックのうち最上位を1つ上げ、残りを最下位に詰める
+            let lowest = mask & mask.wrapping_neg();
+            let carried = mask + lowest;
+            let next = (((carried ^ mask) >> 2) / lowest) | carried;
+            if next < self.limit {
+                Some(next)
+            } else {
+                None
+            }
+        };
+        Some(mask)
+    }
+}
+
+/// 部分集合についてのゼータ変換 f[S] <- Σ_{T ⊆ S} f[T]
+/// fの長さは2のべき乗であること
+///
+/// # Arguments
+/// * `f` - 変換するスライス
+/// * `add` - 加算を行うクロージャ
+pub fn subset_zeta_transform<T, F>(f: &mut [T], mut add: F)
+where
+    F: FnMut(&T, &T) -> T,
+{
+    transform_by_bit(f, |f, s, bit| {
+        if s & bit != 0 {
+            f[s] = add(&f[s], &f[s ^ bit]);
+        }
+    });
+}
+
+/// 部分集合についてのメビウス変換 (subset_zeta_transformの逆変換)
+/// fの長さは2のべき乗であること
+///
+/// # Arguments
+/// * `f` - 変換するスライス
+/// * `sub` - 減算を行うクロージャ
+pub fn subset_mobius_transform<T, F>(f: &mut [T], mut sub: F)
+where
+    F: FnMut(&T, &T) -> T,
+{
+    transform_by_bit(f, |f, s, bit| {
+        if s & bit != 0 {
+            f[s] = sub(&f[s], &f[s ^ bit]);
+        }
+    });
+}
+
+/// 上位集合についてのゼータ変換 f[S] <- Σ_{S ⊆ T} f[T]
+/// fの長さは2のべき乗であること
+///
+/// # Arguments
+/// * `f` - 変換するスライス
+/// * `add` - 加算を行うクロージャ
+pub fn superset_zeta_transform<T, F>(f: &mut [T], mut add: F)
+where
+    F: FnMut(&T, &T) -> T,
+{
+    transform_by_bit(f, |f, s, bit| {
+        if s & bit == 0 {
+            f[s] = add(&f[s], &f[s | bit]);
+        }
+    });
+}
+
+/// 上位集合についてのメビウス変換 (superset_zeta_transformの逆変換)
+/// fの長さは2のべき乗であること
+///
+/// # Arguments
+/// * `f` - 変換するスライス
+/// * `sub` - 減算を行うクロージャ
+pub fn superset_mobius_transform<T, F>(f: &mut [T], mut sub: F)
+where
+    F: FnMut(&T, &T) -> T,
+{
+    transform_by_bit(f, |f, s, bit| {
+        if s & bit == 0 {
+            f[s] = sub(&f[s], &f[s | bit]);
+        }
+    });
+}
+
+// 各ビットについて、全ての集合sにupdate(f, s, bit)を行う
+fn transform_by_bit<T, F>(f: &mut [T], mut update: F)
+where
+    F: FnMut(&mut [T], usize, usize),
+{
+    assert!(f.len().is_power_of_two(), "長さが2のべき乗ではありません");
+    let mut bit = 1;
+    while bit < f.len() {
+        for s in 0..f.len() {
+            update(f, s, bit);
+        }
+        bit <<= 1;
+    }
+}
+
 /// 反復マージソート関数
 /// ソート済みベクタを返却
 ///
@@ -172,7 +1314,79 @@ where
             _ => unreachable!(),
         }
     }
-    q.pop_front()
+    q.pop_front()
+}
+
+/// スライスをその場でマージソートする (安定)
+/// 作業用のバッファを1つだけ確保する
+///
+/// # Arguments
+/// * `v` - ソートを行いたい任意の型のスライス
+/// * `cmp` - 比較用クロージャ |a, b| でaを先頭側に置きたい時trueを返す 等しい時にtrueを返すと安定になる
+pub fn merge_sort_by<T: Clone, F>(v: &mut [T], mut cmp: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    bottom_up_merge_sort(v, &mut cmp);
+}
+
+/// 転倒数 (i < j かつ v[i] > v[j] となる組の数) を求める
+pub fn count_inversions<T: Ord + Clone>(v: &[T]) -> u64 {
+    let mut sorted = v.to_vec();
+    bottom_up_merge_sort(&mut sorted, &mut |a: &T, b: &T| a <= b)
+}
+
+// 幅1から倍々にマージしていくマージソート
+// vとbufferを交互にマージ元・マージ先にし、cmpで後ろ側を先に置いた回数を転倒数として返す
+fn bottom_up_merge_sort<T: Clone, F>(v: &mut [T], cmp: &mut F) -> u64
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    let mut buffer = v.to_vec();
+    let mut inversions = 0;
+    // 最新の並びがbuffer側にあるか
+    let mut in_buffer = false;
+    let mut width = 1;
+    while width < len {
+        let (src, dst): (&[T], &mut [T]) = if in_buffer {
+            (&buffer, v)
+        } else {
+            (v, &mut buffer)
+        };
+        for start in (0..len).step_by(width * 2) {
+            let mid = (start + width).min(len);
+            let end = (start + width * 2).min(len);
+            inversions += merge_runs(&src[start..end], mid - start, &mut dst[start..end], cmp);
+        }
+        in_buffer = !in_buffer;
+        width *= 2;
+    }
+    if in_buffer {
+        v.clone_from_slice(&buffer);
+    }
+    inversions
+}
+
+// ソート済みのsrc[..mid]とsrc[mid..]をdstにマージする
+// 後ろ側の要素を先に置く時、前側に残っている要素数を転倒数として数える
+fn merge_runs<T: Clone, F>(src: &[T], mid: usize, dst: &mut [T], cmp: &mut F) -> u64
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (mut i, mut j) = (0, mid);
+    let mut inversions = 0;
+    for slot in dst.iter_mut() {
+        if j == src.len() || (i < mid && cmp(&src[i], &src[j])) {
+            *slot = src[i].clone();
+            i += 1;
+        } else {
+            *slot = src[j].clone();
+            j += 1;
+            inversions += (mid - i) as u64;
+        }
+    }
+    inversions
 }
 
 /// 不安定反復マージソート関数
@@ -208,15 +1422,134 @@ where
 }
 
 /// 10進数で各桁の和を算出
-pub fn sum_digits(mut v: usize) -> usize {
-    let mut sum = 0;
-    sum += v % 10;
-    v /= 10;
-    while v > 0 {
-        sum += v % 10;
-        v /= 10;
+pub fn sum_digits(v: usize) -> usize {
+    digit_sum(v as u64, 10) as usize
+}
+
+/// nをbase進数で表した各桁を上位桁から順に取得 (nが0の場合は[0])
+/// 基数はdigit_dpと同じくu32で指定する
+pub fn digits(mut n: u64, base: u32) -> Vec<u32> {
+    assert!(base >= 2);
+    let base = base as u64;
+    let mut digits = vec![(n % base) as u32];
+    n /= base;
+    while n > 0 {
+        digits.push((n % base) as u32);
+        n /= base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// 上位桁から順に並んだbase進数の各桁から値を復元する
+pub fn from_digits(digits: &[u32], base: u32) -> u64 {
+    digits.iter().fold(0, |n, &d| n * base as u64 + d as u64)
+}
+
+/// nをbase進数で表した時の各桁の和を算出
+pub fn digit_sum(n: u64, base: u32) -> u64 {
+    digits(n, base).iter().map(|&d| d as u64).sum()
+}
+
+/// 10進数の文字列を上位桁から順に並んだ各桁に変換する
+/// u64に収まらない大きな数をdigit_dpの上限に使う時に用いる
+pub fn digits_from_str(s: &str) -> Vec<u32> {
+    s.chars()
+        .map(|c| c.to_digit(10).expect("10進数の文字列ではありません"))
+        .collect()
+}
+
+/// 桁DP
+/// 上位桁から順に数字を決め、0以上upper以下の整数のうちacceptとなるものの個数を数える
+/// 整数はupperと同じ桁数になるよう先頭を0で埋めて扱うので、先頭の0を区別したい場合は状態に持たせること
+///
+/// # Arguments
+/// * `upper` - 上限の各桁 (上位桁から順)
+/// * `base` - 基数
+/// * `init` - 1桁も決めていない時の状態
+/// * `transition` - 状態と次の桁の数字から次の状態を返すクロージャ 以降を数えない場合はNone
+/// * `accept` - 全桁を決めた後の状態を数えるか判定するクロージャ
+pub fn digit_dp<S, F, A>(upper: &[u32], base: u32, init: S, transition: F, accept: A) -> u64
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S, u32) -> Option<S>,
+    A: FnMut(&S) -> bool,
+{
+    digit_dp_core(upper, base, init, transition, accept, |a, b| a + b)
+}
+
+/// 桁DP 個数をmodulusで割った余りを求める
+/// 引数はdigit_dpと同じ
+pub fn digit_dp_mod<S, F, A>(
+    upper: &[u32],
+    base: u32,
+    init: S,
+    transition: F,
+    accept: A,
+    modulus: u64,
+) -> u64
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S, u32) -> Option<S>,
+    A: FnMut(&S) -> bool,
+{
+    digit_dp_core(upper, base, init, transition, accept, |a, b| {
+        (a + b) % modulus
+    })
+}
+
+// 上限と一致している状態(tight)と、既に上限を下回った状態ごとの個数(loose)を持って桁を進める
+fn digit_dp_core<S, F, A, P>(
+    upper: &[u32],
+    base: u32,
+    init: S,
+    mut transition: F,
+    mut accept: A,
+    mut add: P,
+) -> u64
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S, u32) -> Option<S>,
+    A: FnMut(&S) -> bool,
+    P: FnMut(u64, u64) -> u64,
+{
+    let mut tight: Option<S> = Some(init);
+    let mut loose: HashMap<S, u64> = HashMap::new();
+    for &limit in upper {
+        assert!(limit < base);
+        let mut next: HashMap<S, u64> = HashMap::new();
+        for (state, &count) in &loose {
+            for d in 0..base {
+                if let Some(next_state) = transition(state, d) {
+                    let entry = next.entry(next_state).or_insert(0);
+                    *entry = add(*entry, count);
+                }
+            }
+        }
+        if let Some(state) = tight.take() {
+            for d in 0..limit {
+                if let Some(next_state) = transition(&state, d) {
+                    let entry = next.entry(next_state).or_insert(0);
+                    *entry = add(*entry, 1);
+                }
+            }
+            tight = transition(&state, limit);
+        }
+        loose = next;
+    }
+
+    let mut total = 0;
+    for (state, &count) in &loose {
+        if accept(state) {
+            total = add(total, count);
+        }
     }
-    sum
+    if let Some(state) = tight {
+        if accept(&state) {
+            total = add(total, 1);
+        }
+    }
+    total
 }
 
 // min_value マクロのテスト
@@ -255,12 +1588,54 @@ fn test_debug_print_macro() {
     // デバッグモードでのみ出力されるため、テストは省略
 }
 
+// Scanner のテスト
+#[test]
+fn test_scanner() {
+    let mut sc = Scanner::new("  12 -3\n\nabc 2.5\n");
+    assert_eq!(sc.read::<usize>(), 12);
+    assert_eq!(sc.read::<i64>(), -3);
+    assert_eq!(sc.next_token(), Some("abc"));
+    assert_eq!(sc.read::<f64>(), 2.5);
+    assert_eq!(sc.next_token(), None);
+}
+
+// input マクロのテスト
+#[test]
+fn test_input_macro() {
+    let mut sc = Scanner::new("2 3\n1 2 3\n4 5 6\n#.#\n..#\n1 2\n2 1\nhello\n");
+    input! {
+        from sc,
+        h: usize,
+        w: usize,
+        a: [[i64; w]; h],
+        grid: [Chars; h],
+        mut edges: [(Usize1, Usize1); 2],
+        s: Bytes,
+    }
+    assert_eq!(a, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    assert_eq!(grid, vec![vec!['#', '.', '#'], vec!['.', '.', '#']]);
+    edges.sort();
+    assert_eq!(edges, vec![(0, 1), (1, 0)]);
+    assert_eq!(s, b"hello".to_vec());
+
+    // &mut Scannerからも読み込める
+    let mut sc = Scanner::new("3 x");
+    let sc_ref = &mut sc;
+    input! {
+        from sc_ref,
+        n: u32,
+        c: char
+    }
+    assert_eq!((n, c), (3, 'x'));
+}
+
 // print_vector 関数のテスト
 #[test]
 fn test_print_vector_function() {
     let vec = vec![1, 2, 3, 4, 5];
     let delimiter = ",";
-    print_vector(&vec, &delimiter);
+    print_vector(&vec, delimiter);
+    print_vector::<i32>(&[], delimiter);
     // 標準出力を確認する必要があるため、テストは省略
 }
 
@@ -269,10 +1644,41 @@ fn test_print_vector_function() {
 fn test_print_2d_vector_function() {
     let grid = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
     let delimiter = ",";
-    print_2d_vector(&grid, &delimiter);
+    print_2d_vector(&grid, delimiter);
+    print_2d_vector::<i32>(&[vec![]], delimiter);
     // 標準出力を確認する必要があるため、テストは省略
 }
 
+// Output のテスト
+#[test]
+fn test_output() {
+    let mut written: Vec<u8> = vec![];
+    {
+        let mut out = Output::new(&mut written);
+        out.write_vec(&[1, 2, 3], " ");
+        out.write_grid(&[vec!['a', 'b'], vec!['c', 'd']], "");
+        out.yes_no(true);
+        out.yes_no(false);
+        out.join(vec![1.5, 2.0], ",");
+        writeln!(out, "!").unwrap();
+        // 空のベクタは空行になる
+        out.write_vec::<i32>(&[], " ");
+        out.write_grid::<i32>(&[vec![]], " ");
+    }
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        "1 2 3\nab\ncd\nYes\nNo\n1.5,2!\n\n\n"
+    );
+
+    let mut written: Vec<u8> = vec![];
+    {
+        let mut out = Output::new(&mut written);
+        out.flush_on_drop(false);
+        out.write_vec(&[1], " ");
+    }
+    assert!(written.is_empty());
+}
+
 // cmp_and_replace_value_in_hashmap 関数のテスト
 #[test]
 fn test_cmp_and_replace_value_in_hashmap_function() {
@@ -285,11 +1691,381 @@ fn test_cmp_and_replace_value_in_hashmap_function() {
 // bin_sch 関数のテスト
 #[test]
 fn test_bin_sch_function() {
-    let v = vec![1, 3, 5, 7, 9];
+    let v = [1, 3, 5, 7, 9];
     let result = bin_sch(0, v.len() - 1, |mid| v[mid] <= 5);
     assert!(matches!(result, 2));
 }
 
+// binary_search_by 関数のテスト
+#[test]
+fn test_binary_search_by_function() {
+    let v = [1, 3, 5, 7, 9];
+    assert_eq!(
+        binary_search_by(0, v.len() - 1, Monotone::OkThenNg, |mid| v[mid] <= 5),
+        Some(2)
+    );
+    assert_eq!(
+        binary_search_by(0, v.len() - 1, Monotone::NgThenOk, |mid| v[mid] >= 5),
+        Some(2)
+    );
+    assert_eq!(
+        binary_search_by(0, v.len() - 1, Monotone::OkThenNg, |mid| v[mid] <= 0),
+        None
+    );
+    assert_eq!(
+        binary_search_by(0, v.len() - 1, Monotone::NgThenOk, |mid| v[mid] >= 10),
+        None
+    );
+    assert_eq!(binary_search_by(5, 4, Monotone::OkThenNg, |_| true), None);
+
+    // 1e18までの答え x * x <= 10^18 となる最大のx
+    let limit: i64 = 1_000_000_000_000_000_000;
+    assert_eq!(
+        binary_search_by(1i64, limit, Monotone::OkThenNg, |x| x <= limit / x),
+        Some(1_000_000_000)
+    );
+
+    // 負の範囲
+    assert_eq!(
+        binary_search_by(-100i64, 100, Monotone::NgThenOk, |x| x * 3 >= -50),
+        Some(-16)
+    );
+
+    // 型の端を含む範囲でもオーバーフローしない
+    assert_eq!(
+        binary_search_by(0u64, u64::MAX, Monotone::OkThenNg, |_| true),
+        Some(u64::MAX)
+    );
+    assert_eq!(
+        binary_search_by(i64::MIN, i64::MAX, Monotone::NgThenOk, |_| true),
+        Some(i64::MIN)
+    );
+    assert_eq!(
+        binary_search_by(i8::MIN, i8::MAX, Monotone::OkThenNg, |x| x < 0),
+        Some(-1)
+    );
+    assert_eq!(
+        binary_search_by(u8::MIN, u8::MAX, Monotone::NgThenOk, |x| x > 200),
+        Some(201)
+    );
+}
+
+// bisect 関数のテスト
+#[test]
+fn test_bisect_function() {
+    // x * x <= 2 となる最大のx
+    let sqrt2 = bisect(0.0, 2.0, Termination::Iterations(100), |x| x * x <= 2.0);
+    assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-12);
+    // ok > ngの向き x * x * x >= 10 となる最小のx
+    let cbrt10 = bisect(10.0, 0.0, Termination::Epsilon(1e-9), |x| x * x * x >= 10.0);
+    assert!((cbrt10 - 10f64.cbrt()).abs() < 1e-8);
+    assert!(cbrt10.powi(3) >= 10.0);
+    // 精度の限界より小さいepsでも止まる
+    let large = bisect(0.0, 1e18, Termination::Epsilon(0.0), |x| x <= 3e17);
+    assert_eq!(large, 3e17);
+}
+
+// ternary_search_int 関数のテスト
+#[test]
+fn test_ternary_search_int_function() {
+    assert_eq!(
+        ternary_search_int(-100, 100, Extremum::Minimize, |x| (x - 7) * (x - 7) + 3),
+        (7, 3)
+    );
+    assert_eq!(
+        ternary_search_int(-100i64, 100, Extremum::Maximize, |x| -(x + 42).abs()),
+        (-42, 0)
+    );
+    // 端が最適
+    assert_eq!(
+        ternary_search_int(0, 10, Extremum::Minimize, |x| x * 2),
+        (0, 0)
+    );
+    assert_eq!(
+        ternary_search_int(0, 10, Extremum::Maximize, |x| x * 2),
+        (10, 20)
+    );
+    assert_eq!(ternary_search_int(5, 5, Extremum::Minimize, |x| x), (5, 5));
+    // i64以外の整数型 型の端を含む範囲
+    assert_eq!(
+        ternary_search_int(0u64, u64::MAX, Extremum::Minimize, |x| x.abs_diff(1 << 40)),
+        (1 << 40, 0)
+    );
+    assert_eq!(
+        ternary_search_int(i8::MIN, i8::MAX, Extremum::Maximize, |x| -(x as i32 - 100)
+            .abs()),
+        (100, 0)
+    );
+    // 値の型は整数以外でもよい
+    let (arg, value) = ternary_search_int(0, 1000, Extremum::Minimize, |x| {
+        (x as f64 / 10.0 - 31.4).powi(2)
+    });
+    assert_eq!(arg, 314);
+    assert!(value < 1e-9);
+}
+
+// ternary_search_f64, golden_section_search 関数のテスト
+#[test]
+fn test_ternary_and_golden_section_search_function() {
+    let f = |x: f64| (x - 1.5).powi(2) + 2.0;
+    for &termination in &[Termination::Iterations(200), Termination::Epsilon(1e-9)] {
+        let (arg, value) = ternary_search_f64(-10.0, 10.0, termination, Extremum::Minimize, f);
+        assert!((arg - 1.5).abs() < 1e-6);
+        assert!((value - 2.0).abs() < 1e-9);
+
+        let (arg, value) = golden_section_search(-10.0, 10.0, termination, Extremum::Minimize, f);
+        assert!((arg - 1.5).abs() < 1e-6);
+        assert!((value - 2.0).abs() < 1e-9);
+    }
+
+    let g = |x: f64| x.sin();
+    let (arg, value) = ternary_search_f64(
+        0.0,
+        3.0,
+        Termination::Iterations(100),
+        Extremum::Maximize,
+        g,
+    );
+    assert!((arg - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    assert!((value - 1.0).abs() < 1e-9);
+
+    // 黄金分割探索は1回の縮小で1回しか評価しない
+    let mut evaluations = 0;
+    let (arg, _) = golden_section_search(
+        0.0,
+        3.0,
+        Termination::Iterations(50),
+        Extremum::Maximize,
+        |x| {
+            evaluations += 1;
+            g(x)
+        },
+    );
+    assert!((arg - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    assert_eq!(evaluations, 52);
+}
+
+// Compressor のテスト
+#[test]
+fn test_compressor() {
+    let compressor: Compressor<i64> = vec![100, -5, 30, 100, 7, -5].into_iter().collect();
+    assert_eq!(compressor.len(), 4);
+    assert_eq!(compressor.values(), &[-5, 7, 30, 100]);
+    assert_eq!(compressor.index_of(&30), Some(2));
+    assert_eq!(compressor.index_of(&31), None);
+    assert_eq!(*compressor.value_at(3), 100);
+    assert_eq!(compressor.lower_bound(&7), 1);
+    assert_eq!(compressor.upper_bound(&7), 2);
+    assert_eq!(compressor.lower_bound(&8), 2);
+    assert_eq!(compressor.upper_bound(&8), 2);
+    assert_eq!(compressor.lower_bound(&-100), 0);
+    assert_eq!(compressor.upper_bound(&1000), 4);
+
+    let empty: Compressor<i64> = Vec::new().into_iter().collect();
+    assert!(empty.is_empty());
+    assert_eq!(empty.lower_bound(&0), 0);
+}
+
+// compress 関数のテスト
+#[test]
+fn test_compress_function() {
+    let (indices, values) = compress(&[100, -5, 30, 100, 7, -5]);
+    assert_eq!(indices, vec![3, 0, 2, 3, 1, 0]);
+    assert_eq!(values, vec![-5, 7, 30, 100]);
+
+    let (indices, values) = compress(&["b", "a", "b"]);
+    assert_eq!(indices, vec![1, 0, 1]);
+    assert_eq!(values, vec!["a", "b"]);
+}
+
+// next_permutation, prev_permutation 関数のテスト
+#[test]
+fn test_next_and_prev_permutation_function() {
+    let mut v = vec![1, 2, 3];
+    let mut all = vec![v.clone()];
+    while next_permutation(&mut v) {
+        all.push(v.clone());
+    }
+    assert_eq!(
+        all,
+        vec![
+            vec![1, 2, 3],
+            vec![1, 3, 2],
+            vec![2, 1, 3],
+            vec![2, 3, 1],
+            vec![3, 1, 2],
+            vec![3, 2, 1]
+        ]
+    );
+    // 最後の順列の次は最初の順列に戻る
+    assert_eq!(v, vec![1, 2, 3]);
+
+    assert!(!prev_permutation(&mut v));
+    assert_eq!(v, vec![3, 2, 1]);
+    let mut all_rev = vec![v.clone()];
+    while prev_permutation(&mut v) {
+        all_rev.push(v.clone());
+    }
+    all_rev.reverse();
+    assert_eq!(all_rev, all);
+
+    // 等しい要素があっても同じ並びは1回だけ
+    let mut v = vec!['a', 'a', 'b', 'b'];
+    let mut count = 1;
+    while next_permutation(&mut v) {
+        count += 1;
+    }
+    assert_eq!(count, 6);
+
+    let mut v: Vec<i32> = vec![];
+    assert!(!next_permutation(&mut v));
+    assert!(!prev_permutation(&mut v));
+}
+
+// 順列・組み合わせのイテレータのテスト
+#[test]
+fn test_permutation_and_combination_iterators() {
+    assert_eq!(
+        permutations(&['x', 'y', 'z']).collect::<Vec<_>>(),
+        vec![
+            vec!['x', 'y', 'z'],
+            vec!['x', 'z', 'y'],
+            vec!['y', 'x', 'z'],
+            vec!['y', 'z', 'x'],
+            vec!['z', 'x', 'y'],
+            vec!['z', 'y', 'x']
+        ]
+    );
+    assert_eq!(permutations(&[1, 1, 2]).count(), 6);
+    assert_eq!(permutations::<i32>(&[]).collect::<Vec<_>>(), vec![vec![]]);
+
+    assert_eq!(
+        combinations(&[1, 2, 3, 4], 2).collect::<Vec<_>>(),
+        vec![
+            vec![1, 2],
+            vec![1, 3],
+            vec![1, 4],
+            vec![2, 3],
+            vec![2, 4],
+            vec![3, 4]
+        ]
+    );
+    assert_eq!(combinations(&[0; 10], 4).count(), 210);
+    assert_eq!(combinations(&[1, 2], 0).collect::<Vec<_>>(), vec![vec![]]);
+    assert_eq!(combinations(&[1, 2], 3).count(), 0);
+
+    assert_eq!(
+        combinations_with_replacement(&['a', 'b', 'c'], 2).collect::<Vec<_>>(),
+        vec![
+            vec!['a', 'a'],
+            vec!['a', 'b'],
+            vec!['a', 'c'],
+            vec!['b', 'b'],
+            vec!['b', 'c'],
+            vec!['c', 'c']
+        ]
+    );
+    assert_eq!(combinations_with_replacement(&[0; 5], 3).count(), 35);
+    assert_eq!(combinations_with_replacement::<i32>(&[], 2).count(), 0);
+    assert_eq!(combinations_with_replacement::<i32>(&[], 0).count(), 1);
+
+    assert_eq!(
+        cartesian_product(&[vec![1, 2], vec![10], vec![100, 200]]).collect::<Vec<_>>(),
+        vec![
+            vec![1, 10, 100],
+            vec![1, 10, 200],
+            vec![2, 10, 100],
+            vec![2, 10, 200]
+        ]
+    );
+    assert_eq!(cartesian_product(&[vec![1, 2], vec![]]).count(), 0);
+    assert_eq!(
+        cartesian_product::<i32>(&[]).collect::<Vec<_>>(),
+        vec![vec![]]
+    );
+}
+
+// ビットマスクの列挙のテスト
+#[test]
+fn test_mask_iterators() {
+    assert_eq!(
+        all_masks(3).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4, 5, 6, 7]
+    );
+    assert_eq!(all_masks(0).collect::<Vec<_>>(), vec![0]);
+
+    assert_eq!(
+        submasks(0b1011).collect::<Vec<_>>(),
+        vec![0b1011, 0b1010, 0b1001, 0b1000, 0b0011, 0b0010, 0b0001, 0]
+    );
+    assert_eq!(submasks(0).collect::<Vec<_>>(), vec![0]);
+
+    assert_eq!(
+        supersets(0b0101, 4).collect::<Vec<_>>(),
+        vec![0b0101, 0b0111, 0b1101, 0b1111]
+    );
+    assert_eq!(supersets(0b111, 3).collect::<Vec<_>>(), vec![0b111]);
+
+    assert_eq!(
+        masks_with_k_bits(4, 2).collect::<Vec<_>>(),
+        vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+    );
+    assert_eq!(masks_with_k_bits(3, 0).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(masks_with_k_bits(3, 3).collect::<Vec<_>>(), vec![0b111]);
+    assert_eq!(masks_with_k_bits(3, 4).count(), 0);
+    // 上限いっぱいのビット数
+    let top = usize::BITS as usize - 1;
+    assert_eq!(
+        masks_with_k_bits(top, top).collect::<Vec<_>>(),
+        vec![usize::MAX >> 1]
+    );
+    assert_eq!(masks_with_k_bits(top, 1).count(), top);
+    for n in 0..10 {
+        for k in 0..=n {
+            let expected: Vec<usize> = all_masks(n)
+                .filter(|mask| mask.count_ones() as usize == k)
+                .collect();
+            assert_eq!(masks_with_k_bits(n, k).collect::<Vec<_>>(), expected);
+        }
+    }
+}
+
+// usizeのビット数以上のnは明示的にpanicする
+#[test]
+#[should_panic(expected = "usizeのビット数未満")]
+fn test_masks_with_k_bits_too_wide() {
+    masks_with_k_bits(usize::BITS as usize, 1);
+}
+
+// ゼータ変換・メビウス変換のテスト
+#[test]
+fn test_zeta_and_mobius_transform() {
+    let n = 4;
+    let original: Vec<i64> = all_masks(n).map(|s| (s * s) as i64 % 7 - 3).collect();
+
+    let mut subset_sum = original.clone();
+    subset_zeta_transform(&mut subset_sum, |a, b| a + b);
+    let mut superset_sum = original.clone();
+    superset_zeta_transform(&mut superset_sum, |a, b| a + b);
+    for s in all_masks(n) {
+        let expected: i64 = submasks(s).map(|t| original[t]).sum();
+        assert_eq!(subset_sum[s], expected);
+        let expected: i64 = supersets(s, n).map(|t| original[t]).sum();
+        assert_eq!(superset_sum[s], expected);
+    }
+
+    // メビウス変換で元に戻る
+    subset_mobius_transform(&mut subset_sum, |a, b| a - b);
+    assert_eq!(subset_sum, original);
+    superset_mobius_transform(&mut superset_sum, |a, b| a - b);
+    assert_eq!(superset_sum, original);
+
+    // 加算以外の演算 部分集合の最大値
+    let mut subset_max = original.clone();
+    subset_zeta_transform(&mut subset_max, |a, b| *a.max(b));
+    assert_eq!(subset_max[0b1111], *original.iter().max().unwrap());
+}
+
 // merge_sort 関数のテスト
 #[test]
 fn test_merge_sort_function() {
@@ -421,6 +2197,187 @@ fn test_merge_sort_function() {
     );
 }
 
+// merge_sort_by 関数のテスト
+#[test]
+fn test_merge_sort_by_function() {
+    let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+    merge_sort_by(&mut v, |a, b| a <= b);
+    assert_eq!(v, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+
+    // 降順
+    merge_sort_by(&mut v, |a, b| a >= b);
+    assert_eq!(v, vec![9, 6, 5, 5, 5, 4, 3, 3, 2, 1, 1]);
+
+    // 空・1要素
+    let mut v: Vec<i32> = vec![];
+    merge_sort_by(&mut v, |a, b| a <= b);
+    assert!(v.is_empty());
+    let mut v = vec![1];
+    merge_sort_by(&mut v, |a, b| a <= b);
+    assert_eq!(v, vec![1]);
+
+    // 安定ソートの確認
+    let mut v_with_index = vec![
+        (3, 0),
+        (1, 1),
+        (4, 2),
+        (1, 3),
+        (5, 4),
+        (9, 5),
+        (2, 6),
+        (6, 7),
+        (5, 8),
+        (3, 9),
+        (5, 10),
+    ];
+    let mut expected = v_with_index.clone();
+    expected.sort_by_key(|&(a, _)| a);
+    merge_sort_by(&mut v_with_index, |&(a, _), &(b, _)| a <= b);
+    assert_eq!(v_with_index, expected);
+
+    // 様々な長さで標準のソートと比較
+    for len in 0..70 {
+        let v: Vec<u64> = (0..len as u64).map(|i| i * 7919 % 31).collect();
+        let mut sorted = v.clone();
+        merge_sort_by(&mut sorted, |a, b| a <= b);
+        let mut expected = v.clone();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+}
+
+// count_inversions 関数のテスト
+#[test]
+fn test_count_inversions_function() {
+    assert_eq!(count_inversions(&[3, 1, 2]), 2);
+    assert_eq!(count_inversions(&[1, 2, 3, 4]), 0);
+    assert_eq!(count_inversions(&[4, 3, 2, 1]), 6);
+    // 等しい要素は転倒として数えない
+    assert_eq!(count_inversions(&[2, 2, 1, 1]), 4);
+    assert_eq!(count_inversions::<i32>(&[]), 0);
+
+    for len in 0..70 {
+        let v: Vec<u64> = (0..len as u64).map(|i| i * 7919 % 31).collect();
+        let mut naive = 0;
+        for i in 0..len {
+            for j in i + 1..len {
+                if v[i] > v[j] {
+                    naive += 1;
+                }
+            }
+        }
+        assert_eq!(count_inversions(&v), naive);
+    }
+}
+
+// radix_sort 関数のテスト
+#[test]
+fn test_radix_sort_function() {
+    let mut v: Vec<u32> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+    radix_sort(&mut v);
+    assert_eq!(v, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+
+    // 負の数を含む
+    let mut v: Vec<i64> = vec![5, -3, 0, i64::MIN, 1 << 40, -(1 << 40), i64::MAX, -1];
+    radix_sort(&mut v);
+    assert_eq!(
+        v,
+        vec![i64::MIN, -(1 << 40), -3, -1, 0, 5, 1 << 40, i64::MAX]
+    );
+
+    // 空
+    let mut v: Vec<u64> = vec![];
+    radix_sort(&mut v);
+    assert!(v.is_empty());
+
+    // 標準のソートと比較
+    let v: Vec<u64> = (0..1000u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect();
+    let mut sorted = v.clone();
+    radix_sort(&mut sorted);
+    let mut expected = v;
+    expected.sort();
+    assert_eq!(sorted, expected);
+
+    // 安定ソートの確認
+    let mut v_with_index: Vec<(i32, usize)> = vec![
+        (3, 0),
+        (1, 1),
+        (4, 2),
+        (1, 3),
+        (5, 4),
+        (9, 5),
+        (2, 6),
+        (6, 7),
+        (5, 8),
+        (3, 9),
+        (5, 10),
+    ];
+    radix_sort_by_key(&mut v_with_index, |&(a, _)| a as u32);
+    assert_eq!(
+        v_with_index,
+        vec![
+            (1, 1),
+            (1, 3),
+            (2, 6),
+            (3, 0),
+            (3, 9),
+            (4, 2),
+            (5, 4),
+            (5, 8),
+            (5, 10),
+            (6, 7),
+            (9, 5)
+        ]
+    );
+}
+
+// counting_sort_by_key 関数のテスト
+#[test]
+fn test_counting_sort_by_key_function() {
+    let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+    counting_sort_by_key(&mut v, 10, |&x| x);
+    assert_eq!(v, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+
+    // 空
+    let mut v: Vec<usize> = vec![];
+    counting_sort_by_key(&mut v, 0, |&x| x);
+    assert!(v.is_empty());
+
+    // 安定ソートの確認
+    let mut v_with_index: Vec<(usize, usize)> = vec![
+        (3, 0),
+        (1, 1),
+        (4, 2),
+        (1, 3),
+        (5, 4),
+        (9, 5),
+        (2, 6),
+        (6, 7),
+        (5, 8),
+        (3, 9),
+        (5, 10),
+    ];
+    counting_sort_by_key(&mut v_with_index, 10, |&(a, _)| a);
+    assert_eq!(
+        v_with_index,
+        vec![
+            (1, 1),
+            (1, 3),
+            (2, 6),
+            (3, 0),
+            (3, 9),
+            (4, 2),
+            (5, 4),
+            (5, 8),
+            (5, 10),
+            (6, 7),
+            (9, 5)
+        ]
+    );
+}
+
 // merge_sort_unstable 関数のテスト
 #[test]
 fn test_merge_sort_unstable_function() {
@@ -457,3 +2414,79 @@ fn test_sum_digits() {
     let res = sum_digits(457);
     assert_eq!(16, res);
 }
+
+// digits, from_digits, digit_sum 関数のテスト
+#[test]
+fn test_digits_functions() {
+    assert_eq!(digits(457, 10), vec![4, 5, 7]);
+    assert_eq!(digits(0, 10), vec![0]);
+    assert_eq!(digits(10, 2), vec![1, 0, 1, 0]);
+    assert_eq!(digits(255, 16), vec![15, 15]);
+    assert_eq!(from_digits(&[1, 0, 1, 0], 2), 10);
+    assert_eq!(from_digits(&digits(u64::MAX, 7), 7), u64::MAX);
+    // u32の最大の基数でも桁が切り捨てられない
+    assert_eq!(digits(u64::MAX, u32::MAX), vec![1, 2, 0]);
+    assert_eq!(from_digits(&[1, 2, 0], u32::MAX), u64::MAX);
+    assert_eq!(digit_sum(457, 10), 16);
+    assert_eq!(digit_sum(255, 2), 8);
+    assert_eq!(
+        digits_from_str("123456789012345678901234567890")[..5],
+        [1, 2, 3, 4, 5]
+    );
+}
+
+// digit_dp 関数のテスト
+#[test]
+fn test_digit_dp_function() {
+    // 各桁の和がdで割り切れる0以上n以下の整数の個数
+    let count_divisible = |n: u64, d: usize| {
+        digit_dp(
+            &digits(n, 10),
+            10,
+            0,
+            |&sum: &usize, digit| Some((sum + digit as usize) % d),
+            |&sum| sum == 0,
+        )
+    };
+    for &(n, d) in &[(0, 3), (9, 3), (100, 7), (1234, 5), (2024, 9)] {
+        let naive = (0..=n as usize).filter(|&x| sum_digits(x) % d < 1).count();
+        assert_eq!(count_divisible(n, d), naive as u64);
+    }
+
+    // 先頭の0を区別する状態 (始まったか, 直前の数字) で、隣り合う桁が全て異なる1以上n以下の整数
+    let n = 3000;
+    let count = digit_dp(
+        &digits(n, 10),
+        10,
+        (false, 0),
+        |&(started, last): &(bool, u32), digit| {
+            if !started {
+                Some((digit != 0, digit))
+            } else if digit == last {
+                None
+            } else {
+                Some((true, digit))
+            }
+        },
+        |&(started, _)| started,
+    );
+    let naive = (1..=n)
+        .filter(|&x| digits(x, 10).windows(2).all(|w| w[0] != w[1]))
+        .count();
+    assert_eq!(count, naive as u64);
+
+    // 大きな上限 10^30以下で各桁の和が9の倍数の整数は (10^30 / 9の切り捨て) + 1個
+    let modulus = 998_244_353;
+    let upper = digits_from_str(&format!("1{}", "0".repeat(30)));
+    let count = digit_dp_mod(
+        &upper,
+        10,
+        0,
+        |&sum: &u32, digit| Some((sum + digit) % 9),
+        |&sum| sum == 0,
+        modulus,
+    );
+    // 10^30 / 9 = 111...1 (30桁)
+    let expected = (0..30).fold(0, |acc, _| (acc * 10 + 1) % modulus) + 1;
+    assert_eq!(count, expected % modulus);
+}
@@ -1,24 +1,200 @@
+use std::fmt::Debug;
+
+/// WeightedUnionFindの重みとして使える群
+/// 可換である必要はない
+pub trait Group: Clone + PartialEq + Debug {
+    /// 単位元
+    fn identity() -> Self;
+    /// 演算 self・rhs
+    fn op(&self, rhs: &Self) -> Self;
+    /// 逆元
+    fn inverse(&self) -> Self;
+}
+
+impl Group for isize {
+    fn identity() -> Self {
+        0
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        self + rhs
+    }
+    fn inverse(&self) -> Self {
+        -self
+    }
+}
+
+impl Group for i64 {
+    fn identity() -> Self {
+        0
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        self + rhs
+    }
+    fn inverse(&self) -> Self {
+        -self
+    }
+}
+
+/// XORを演算とする群 (偶奇・二部グラフ判定など)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xor(pub u64);
+
+impl Group for Xor {
+    fn identity() -> Self {
+        Xor(0)
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        Xor(self.0 ^ rhs.0)
+    }
+    fn inverse(&self) -> Self {
+        *self
+    }
+}
+
+/// mod Mの加法群 値はnewや演算の結果では0..Mで保持する
+/// ModAdd(v)で直接作った場合もv mod Mとして比較する
 #[derive(Debug, Clone, Copy)]
-enum WeightedUnionFindNode {
-    ParentAndWeight(usize, isize), //親ノード番号と親への重み
+pub struct ModAdd<const M: u64>(pub u64);
+
+impl<const M: u64> ModAdd<M> {
+    /// v mod Mを値に持つ要素を作成
+    pub fn new(v: u64) -> Self {
+        ModAdd(v % M)
+    }
+}
+
+impl<const M: u64> PartialEq for ModAdd<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 % M == other.0 % M
+    }
+}
+
+impl<const M: u64> Eq for ModAdd<M> {}
+
+impl<const M: u64> Group for ModAdd<M> {
+    fn identity() -> Self {
+        ModAdd(0)
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        // M > 2^63でも溢れないようu128で計算する
+        ModAdd(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+    fn inverse(&self) -> Self {
+        ModAdd::new(M - self.0 % M)
+    }
+}
+
+/// 正の実数の乗法群 (比率)
+/// 比較は相対誤差1e-9まで等しいとみなす
+#[derive(Debug, Clone, Copy)]
+pub struct Ratio(pub f64);
+
+impl PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        let scale = self.0.abs().max(other.0.abs()).max(1.0);
+        (self.0 - other.0).abs() <= 1e-9 * scale
+    }
+}
+
+impl Group for Ratio {
+    fn identity() -> Self {
+        Ratio(1.0)
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        Ratio(self.0 * rhs.0)
+    }
+    fn inverse(&self) -> Self {
+        Ratio(1.0 / self.0)
+    }
+}
+
+/// 置換群 (非可換)
+/// p.0[i]はiの移り先 範囲外のインデックスは不動点として扱う
+/// a.op(b)は「aを適用した後にbを適用する」置換
+#[derive(Debug, Clone)]
+pub struct Permutation(pub Vec<usize>);
+
+impl Permutation {
+    fn apply(&self, i: usize) -> usize {
+        self.0.get(i).copied().unwrap_or(i)
+    }
+}
+
+impl PartialEq for Permutation {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.0.len().max(other.0.len());
+        (0..len).all(|i| self.apply(i) == other.apply(i))
+    }
+}
+
+impl Group for Permutation {
+    fn identity() -> Self {
+        Permutation(vec![])
+    }
+    fn op(&self, rhs: &Self) -> Self {
+        let len = self.0.len().max(rhs.0.len());
+        Permutation((0..len).map(|i| rhs.apply(self.apply(i))).collect())
+    }
+    fn inverse(&self) -> Self {
+        let mut inv: Vec<usize> = (0..self.0.len()).collect();
+        for (i, &to) in self.0.iter().enumerate() {
+            inv[to] = i;
+        }
+        Permutation(inv)
+    }
+}
+
+/// WeightedUnionFind::mergeが成功した時の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// 別グループを結合した
+    Merged,
+    /// 既に同一グループで、weightに矛盾がなかった
+    Redundant,
+}
+
+/// WeightedUnionFind::mergeでweightが矛盾した時の情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contradiction<G: Group> {
+    /// 既存の関係から定まるnode_aから見たnode_bの重み
+    pub existing: G,
+    /// mergeで指定された重み
+    pub requested: G,
+    /// node_aとnode_bが属するグループのroot_node
+    pub root: usize,
+}
+
+#[derive(Debug, Clone)]
+enum WeightedUnionFindNode<G: Group> {
+    ParentAndWeight(usize, G), //親ノード番号と親への重み
     Size(usize),
 }
 
+/// 重み付きUnionFind
+/// nodeのポテンシャルをp(node)としたとき、merge(a, b, w)は p(b) = p(a)・w を表す
+/// Gを省略した場合はisizeの加法群 (p(b) - p(a) = w)
 #[derive(Debug, Clone)]
-pub struct WeightedUnionFind {
+pub struct WeightedUnionFind<G: Group = isize> {
     // ルートノードであるとき->グループのサイズ
     // ルートノードでないとき->親と親への重み
-    graph: Vec<WeightedUnionFindNode>,
+    graph: Vec<WeightedUnionFindNode<G>>,
     root_count: usize,
 }
 
 impl WeightedUnionFind {
+    /// 重みをisizeとして作成
     pub fn new(size: usize) -> Self {
-        let new_union_find = Self {
+        Self::with_group(size)
+    }
+}
+
+impl<G: Group> WeightedUnionFind<G> {
+    /// 重みを任意の群Gとして作成
+    pub fn with_group(size: usize) -> Self {
+        Self {
             graph: vec![WeightedUnionFindNode::Size(1); size],
             root_count: size,
-        };
-        new_union_find
+        }
     }
 
     /// ルートの数を取得
@@ -27,28 +203,41 @@ impl WeightedUnionFind {
     }
 
     /// node_aとnode_bそれぞれを含むグループを結合する
-    /// true: 結合成功時、もしくは既結合かつweightに矛盾がない時
-    /// false: 既結合かつweightに矛盾がある時
-    pub fn merge(&mut self, node_a: usize, node_b: usize, weight: isize) -> bool {
+    /// Ok(Merged): 結合成功時
+    /// Ok(Redundant): 既結合かつweightに矛盾がない時
+    /// Err(Contradiction): 既結合かつweightに矛盾がある時
+    pub fn merge(
+        &mut self,
+        node_a: usize,
+        node_b: usize,
+        weight: G,
+    ) -> Result<MergeOutcome, Contradiction<G>> {
         let (root_a, size_a, weight_a_to_root) = self.get_root_size_weight(node_a);
         let (root_b, size_b, weight_b_to_root) = self.get_root_size_weight(node_b);
 
         if root_a != root_b {
             self.root_count -= 1;
+            // root_bのroot_aに対する重み
+            let weight_rb_from_ra = weight_a_to_root.op(&weight).op(&weight_b_to_root.inverse());
             let (root_bigger, root_smaller, set_weight) = if size_a >= size_b {
-                (root_a, root_b, weight + weight_a_to_root - weight_b_to_root)
+                (root_a, root_b, weight_rb_from_ra)
             } else {
-                (root_b, root_a, weight_b_to_root - weight - weight_a_to_root)
+                (root_b, root_a, weight_rb_from_ra.inverse())
             };
             self.graph[root_smaller] =
                 WeightedUnionFindNode::ParentAndWeight(root_bigger, set_weight);
             self.graph[root_bigger] = WeightedUnionFindNode::Size(size_a + size_b);
-            true
+            Ok(MergeOutcome::Merged)
         } else {
-            if (weight_b_to_root - weight_a_to_root) == weight {
-                true
+            let existing = weight_a_to_root.inverse().op(&weight_b_to_root);
+            if existing == weight {
+                Ok(MergeOutcome::Redundant)
             } else {
-                false
+                Err(Contradiction {
+                    existing,
+                    requested: weight,
+                    root: root_a,
+                })
             }
         }
     }
@@ -58,24 +247,44 @@ impl WeightedUnionFind {
         self.get_root_size_weight(node_a).0 == self.get_root_size_weight(node_b).0
     }
 
+    /// node_aから見たnode_bの重み p(a)^-1・p(b) を取得 (isizeなら p(b) - p(a))
+    /// merge(a, b, w)の後は Some(w) を返す
+    /// node_aとnode_bが別グループの場合はNone
+    pub fn diff(&mut self, node_a: usize, node_b: usize) -> Option<G> {
+        let (root_a, _, weight_a) = self.get_root_size_weight(node_a);
+        let (root_b, _, weight_b) = self.get_root_size_weight(node_b);
+        if root_a == root_b {
+            Some(weight_a.inverse().op(&weight_b))
+        } else {
+            None
+        }
+    }
+
+    /// rootから見たnodeの重み p(root)^-1・p(node) を取得 (isizeなら p(node) - p(root))
+    /// diff(root, node) と等しい
+    pub fn weight(&mut self, node: usize) -> G {
+        self.get_root_size_weight(node).2
+    }
+
     /// 以下を取得
     /// - nodeのroot_node
     /// - nodeが属するグループのsize
-    /// - nodeからrootまでのweight
+    /// - nodeからrootまでのweight (p(root)^-1・p(node))
+    ///
     /// nodeの親をroot_nodeに再設定 (経路圧縮)
-    pub fn get_root_size_weight(&mut self, node: usize) -> (usize, usize, isize) {
+    pub fn get_root_size_weight(&mut self, node: usize) -> (usize, usize, G) {
         let mut node_tmp: usize = node;
-        let mut weight_tmp: isize = 0;
+        let mut weight_tmp: G = G::identity();
         loop {
-            match self.graph[node_tmp] {
+            match &self.graph[node_tmp] {
                 WeightedUnionFindNode::ParentAndWeight(parent, weight) => {
-                    weight_tmp += weight;
-                    node_tmp = parent;
+                    weight_tmp = weight.op(&weight_tmp);
+                    node_tmp = *parent;
                 }
-                WeightedUnionFindNode::Size(size) => {
+                &WeightedUnionFindNode::Size(size) => {
                     if node != node_tmp {
                         self.graph[node] =
-                            WeightedUnionFindNode::ParentAndWeight(node_tmp, weight_tmp);
+                            WeightedUnionFindNode::ParentAndWeight(node_tmp, weight_tmp.clone());
                     }
                     return (node_tmp, size, weight_tmp);
                 }
@@ -97,18 +306,47 @@ mod tests {
     #[test]
     fn test_merge() {
         let mut uf = WeightedUnionFind::new(5);
-        assert!(uf.merge(0, 1, 2));
-        assert!(uf.merge(2, 3, 3));
-        assert!(uf.merge(1, 3, 5));
-        assert!(!uf.merge(1, 3, 3));
+        assert_eq!(uf.merge(0, 1, 2), Ok(MergeOutcome::Merged));
+        assert_eq!(uf.merge(2, 3, 3), Ok(MergeOutcome::Merged));
+        assert_eq!(uf.merge(1, 3, 5), Ok(MergeOutcome::Merged));
+        assert_eq!(uf.merge(1, 3, 5), Ok(MergeOutcome::Redundant));
+        assert_eq!(uf.merge(3, 0, -7), Ok(MergeOutcome::Redundant));
+        assert!(uf.merge(1, 3, 3).is_err());
+        assert_eq!(uf.get_root_count(), 2);
+    }
+
+    #[test]
+    fn test_merge_contradiction() {
+        let mut uf = WeightedUnionFind::new(4);
+        uf.merge(0, 1, 2).unwrap();
+        uf.merge(1, 2, 3).unwrap();
+        let root = uf.get_root_size_weight(0).0;
+        assert_eq!(
+            uf.merge(0, 2, 4),
+            Err(Contradiction {
+                existing: 5,
+                requested: 4,
+                root,
+            })
+        );
+        assert_eq!(
+            uf.merge(2, 0, 0),
+            Err(Contradiction {
+                existing: -5,
+                requested: 0,
+                root,
+            })
+        );
+        // 矛盾した場合は状態が変わらない
+        assert_eq!(uf.diff(0, 2), Some(5));
         assert_eq!(uf.get_root_count(), 2);
     }
 
     #[test]
     fn test_same() {
         let mut uf = WeightedUnionFind::new(5);
-        uf.merge(0, 1, 2);
-        uf.merge(2, 3, 3);
+        uf.merge(0, 1, 2).unwrap();
+        uf.merge(2, 3, 3).unwrap();
         assert!(uf.same(0, 1));
         assert!(!uf.same(1, 4));
     }
@@ -116,11 +354,113 @@ mod tests {
     #[test]
     fn test_get_root_size_weight() {
         let mut uf = WeightedUnionFind::new(5);
-        uf.merge(0, 1, 2);
-        uf.merge(2, 3, 3);
+        uf.merge(0, 1, 2).unwrap();
+        uf.merge(2, 3, 3).unwrap();
         let (root, size, weight) = uf.get_root_size_weight(3);
         assert_eq!(root, 2);
         assert_eq!(size, 2);
         assert_eq!(weight, 3);
     }
+
+    #[test]
+    fn test_diff() {
+        let mut uf = WeightedUnionFind::new(5);
+        uf.merge(0, 1, 2).unwrap();
+        uf.merge(1, 2, -5).unwrap();
+        uf.merge(3, 2, 4).unwrap();
+        // merge(a, b, w) の後は diff(a, b) == Some(w)
+        assert_eq!(uf.diff(0, 1), Some(2));
+        assert_eq!(uf.diff(1, 2), Some(-5));
+        assert_eq!(uf.diff(3, 2), Some(4));
+        // 向きを逆にすると符号が反転する
+        assert_eq!(uf.diff(1, 0), Some(-2));
+        assert_eq!(uf.diff(0, 2), Some(-3));
+        assert_eq!(uf.diff(0, 3), Some(-7));
+        assert_eq!(uf.diff(3, 3), Some(0));
+        // 別グループ
+        assert_eq!(uf.diff(0, 4), None);
+    }
+
+    #[test]
+    fn test_weight() {
+        let mut uf = WeightedUnionFind::new(4);
+        uf.merge(0, 1, 3).unwrap();
+        uf.merge(1, 2, 4).unwrap();
+        let root = uf.get_root_size_weight(2).0;
+        assert_eq!(uf.weight(root), 0);
+        for node in 0..3 {
+            assert_eq!(Some(uf.weight(node)), uf.diff(root, node));
+        }
+        assert_eq!(uf.weight(2) - uf.weight(0), 7);
+        assert_eq!(uf.weight(3), 0);
+    }
+
+    #[test]
+    fn test_mod_add_weight() {
+        let mut uf = WeightedUnionFind::<ModAdd<7>>::with_group(4);
+        assert!(uf.merge(0, 1, ModAdd(5)).is_ok());
+        assert!(uf.merge(1, 2, ModAdd(4)).is_ok());
+        // 5 + 4 = 9 ≡ 2 (mod 7)
+        assert!(uf.merge(0, 2, ModAdd(2)).is_ok());
+        assert!(uf.merge(0, 2, ModAdd(3)).is_err());
+        assert_eq!(uf.diff(0, 2), Some(ModAdd(2)));
+        assert_eq!(uf.diff(2, 0), Some(ModAdd(5)));
+
+        // 0..Mに収まっていない重みも剰余で比較する
+        let mut uf = WeightedUnionFind::<ModAdd<7>>::with_group(3);
+        assert_eq!(uf.merge(0, 1, ModAdd(9)), Ok(MergeOutcome::Merged));
+        assert_eq!(uf.merge(0, 1, ModAdd(9)), Ok(MergeOutcome::Redundant));
+        assert_eq!(uf.merge(0, 1, ModAdd(16)), Ok(MergeOutcome::Redundant));
+        assert!(uf.merge(0, 1, ModAdd(3)).is_err());
+        assert_eq!(uf.diff(0, 1), Some(ModAdd::new(2)));
+        assert_eq!(ModAdd::<7>::new(9).0, 2);
+
+        // M > 2^63でも演算が溢れない
+        const BIG: u64 = u64::MAX - 58;
+        let a = ModAdd::<BIG>::new(BIG - 1);
+        assert_eq!(a.op(&a), ModAdd(BIG - 2));
+        assert_eq!(a.op(&a.inverse()), ModAdd::identity());
+    }
+
+    #[test]
+    fn test_xor_weight() {
+        // 0-1, 1-2, 2-0 の奇閉路は矛盾する
+        let mut uf = WeightedUnionFind::<Xor>::with_group(4);
+        assert!(uf.merge(0, 1, Xor(1)).is_ok());
+        assert!(uf.merge(1, 2, Xor(1)).is_ok());
+        assert!(uf.merge(2, 0, Xor(1)).is_err());
+        assert!(uf.merge(2, 0, Xor(0)).is_ok());
+        assert!(uf.merge(2, 3, Xor(1)).is_ok());
+        assert!(uf.merge(0, 3, Xor(1)).is_ok());
+    }
+
+    #[test]
+    fn test_ratio_weight() {
+        // p(1) = p(0) * 2, p(2) = p(1) * 1.5
+        let mut uf = WeightedUnionFind::<Ratio>::with_group(3);
+        assert!(uf.merge(0, 1, Ratio(2.0)).is_ok());
+        assert!(uf.merge(1, 2, Ratio(1.5)).is_ok());
+        assert!(uf.merge(0, 2, Ratio(3.0)).is_ok());
+        assert!(uf.merge(2, 0, Ratio(1.0 / 3.0)).is_ok());
+        assert!(uf.merge(0, 2, Ratio(3.1)).is_err());
+    }
+
+    #[test]
+    fn test_permutation_weight() {
+        let a = Permutation(vec![1, 2, 0]);
+        let b = Permutation(vec![1, 0]);
+        // 非可換であることの確認
+        assert_ne!(a.op(&b), b.op(&a));
+        assert_eq!(a.op(&a.inverse()), Permutation::identity());
+
+        let mut uf = WeightedUnionFind::<Permutation>::with_group(4);
+        assert!(uf.merge(0, 1, a.clone()).is_ok());
+        assert!(uf.merge(2, 3, b.clone()).is_ok());
+        assert!(uf.merge(1, 2, a.clone()).is_ok());
+        // p(3) = p(0)・a・a・b
+        assert!(uf.merge(0, 3, a.op(&a).op(&b)).is_ok());
+        assert!(uf.merge(0, 3, b.op(&a).op(&a)).is_err());
+        assert!(uf.merge(3, 0, a.op(&a).op(&b).inverse()).is_ok());
+        assert_eq!(uf.get_root_count(), 1);
+    }
 }